@@ -0,0 +1,173 @@
+use std::collections::HashSet;
+
+use crate::{geometry::Point, grid::Grid};
+
+/// how live cells evolve, given the generation about to be computed, counted from 0
+pub trait Rule {
+    fn next(&self, cells: &HashSet<Point>, generation: usize) -> HashSet<Point>;
+}
+
+impl<F: Fn(&HashSet<Point>, usize) -> HashSet<Point>> Rule for F {
+    fn next(&self, cells: &HashSet<Point>, generation: usize) -> HashSet<Point> {
+        self(cells, generation)
+    }
+}
+
+/// live cells on an unbounded plane, evolving one generation at a time under `rule`
+#[derive(Debug, Clone)]
+pub struct Automaton<R> {
+    cells: HashSet<Point>,
+    generation: usize,
+    rule: R,
+}
+
+impl<R: Rule> Automaton<R> {
+    pub fn new(cells: impl IntoIterator<Item = Point>, rule: R) -> Self {
+        Self {
+            cells: cells.into_iter().collect(),
+            generation: 0,
+            rule,
+        }
+    }
+    pub fn cells(&self) -> &HashSet<Point> {
+        &self.cells
+    }
+    /// generations computed so far
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+    /// computes the next generation, returning whether any cell changed
+    pub fn step(&mut self) -> bool {
+        let next = self.rule.next(&self.cells, self.generation);
+        self.generation += 1;
+        let changed = next != self.cells;
+        self.cells = next;
+        changed
+    }
+    /// computes `n` more generations
+    pub fn run(&mut self, n: usize) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+    /// computes generations until `stop` holds for the previous and the latest one,
+    /// returning the number of the generation it stopped at
+    pub fn run_until(&mut self, stop: impl Fn(&HashSet<Point>, &HashSet<Point>) -> bool) -> usize {
+        loop {
+            let next = self.rule.next(&self.cells, self.generation);
+            self.generation += 1;
+            let previous = std::mem::replace(&mut self.cells, next);
+            if stop(&previous, &self.cells) {
+                return self.generation;
+            }
+        }
+    }
+    /// computes generations until one leaves every cell in place, returning its number
+    pub fn settle(&mut self) -> usize {
+        self.run_until(|previous, next| previous == next)
+    }
+    /// top-left and bottom-right corners of the smallest rectangle around live cells
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        let xs = self.cells.iter().map(|x| x.x);
+        let ys = self.cells.iter().map(|x| x.y);
+        Some((
+            Point::new(xs.clone().min()?, ys.clone().min()?),
+            Point::new(xs.max()?, ys.max()?),
+        ))
+    }
+    /// dead cells within the bounds
+    pub fn empty(&self) -> usize {
+        match self.bounds() {
+            Some((least, greatest)) => {
+                let area = (greatest.x - least.x + 1) * (greatest.y - least.y + 1);
+                area as usize - self.cells.len()
+            }
+            None => 0,
+        }
+    }
+    /// latest generation within its bounds, one row per line, with `#` for live cells and `.` for dead ones
+    pub fn render(&self) -> String {
+        let Some((least, greatest)) = self.bounds() else {
+            return String::new();
+        };
+        let width = (greatest.x - least.x + 1) as usize;
+        let height = (greatest.y - least.y + 1) as usize;
+        let mut grid = Grid::new(width, height, false);
+        for at in self.cells.iter() {
+            grid[Point::new(at.x - least.x, at.y - least.y)] = true;
+        }
+        grid.render(|x| if *x { '#' } else { '.' })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::geometry::{Direction, Point};
+
+    use super::Automaton;
+
+    /// Conway's game of life
+    fn life(cells: &HashSet<Point>, _: usize) -> HashSet<Point> {
+        let candidates: HashSet<Point> = cells
+            .iter()
+            .flat_map(|x| x.neighbors(&Direction::ALL).chain([*x]).collect::<Vec<_>>())
+            .collect();
+        candidates
+            .into_iter()
+            .filter(|x| {
+                let live = x
+                    .neighbors(&Direction::ALL)
+                    .filter(|y| cells.contains(y))
+                    .count();
+                live == 3 || (live == 2 && cells.contains(x))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn blinker() {
+        let mut automaton = Automaton::new((0..3).map(|x| Point::new(x, 0)), life);
+        assert_eq!(automaton.render(), "###");
+        assert!(automaton.step());
+        assert_eq!(automaton.render(), "#\n#\n#");
+        assert_eq!(
+            automaton.bounds(),
+            Some((Point::new(1, -1), Point::new(1, 1)))
+        );
+        automaton.run(3);
+        assert_eq!(automaton.generation(), 4);
+        assert_eq!(automaton.render(), "###");
+        assert_eq!(automaton.empty(), 0);
+    }
+
+    #[test]
+    fn stop() {
+        // a block among dying cells, which stays once they are gone
+        let cells = [(0, 0), (1, 0), (0, 1), (1, 1), (5, 5)].map(Point::from);
+        let mut automaton = Automaton::new(cells, life);
+        assert_eq!(automaton.empty(), 31);
+        assert_eq!(automaton.settle(), 2);
+        assert_eq!(automaton.render(), "##\n##");
+        // a pair of cells drifting right then down, in turns
+        let drift = |cells: &HashSet<Point>, generation: usize| {
+            let toward = match generation % 2 {
+                0 => Direction::Right,
+                _ => Direction::Down,
+            };
+            cells.iter().map(|x| x.step(toward)).collect()
+        };
+        let mut automaton = Automaton::new([Point::new(0, 0), Point::new(1, 1)], drift);
+        assert_eq!(
+            automaton.run_until(|_, next| next.iter().all(|x| x.x >= 3)),
+            5
+        );
+        assert_eq!(automaton.render(), "#.\n.#");
+        assert_eq!(
+            automaton.bounds(),
+            Some((Point::new(3, 2), Point::new(4, 3)))
+        );
+        assert_eq!(Automaton::new([], life).render(), "");
+    }
+}
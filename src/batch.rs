@@ -0,0 +1,189 @@
+use std::fmt::Display;
+
+use colored::Colorize;
+
+use crate::{diagnostics::Diagnostic, input::NotFound, registry::Day, solution::Answer};
+
+/// answers of one day for several input sets, printed as a table
+#[derive(Debug)]
+pub struct Batch {
+    pub day: u8,
+    pub parts: Vec<u8>,
+    /// input set along with the outcome of each part
+    pub rows: Vec<(String, Vec<Result<Answer, String>>)>,
+}
+
+impl Batch {
+    /// runs every part of `day` for each named input, or records why it could not be read
+    pub fn run<I>(day: &Day, inputs: I) -> Self
+    where
+        I: IntoIterator<Item = (String, anyhow::Result<String>)>,
+    {
+        let parts: Vec<u8> = (1..=day.parts).collect();
+        let rows = inputs
+            .into_iter()
+            .map(|(set, input)| {
+                let outcomes = parts
+                    .iter()
+                    .map(|part| match &input {
+                        Ok(input) => day.run(*part, input).map_err(|e| reason(&e)),
+                        Err(e) => Err(reason(e)),
+                    })
+                    .collect();
+                (set, outcomes)
+            })
+            .collect();
+        Self {
+            day: day.number,
+            parts,
+            rows,
+        }
+    }
+}
+
+/// short reason for a failure, fitting in a cell
+fn reason(e: &anyhow::Error) -> String {
+    if let Some(diagnostic) = e.downcast_ref::<Diagnostic>() {
+        return format!("{}: {}", diagnostic.span, diagnostic.message);
+    }
+    if let Some(e) = e.downcast_ref::<NotFound>() {
+        return match e.missing() {
+            true => "no input".to_string(),
+            false => "unreadable input".to_string(),
+        };
+    }
+    format!("{e:#}")
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+impl Display for Batch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // cells may span several lines, e.g. a picture
+        let header: Vec<Vec<String>> = std::iter::once(vec!["set".to_string()])
+            .chain(self.parts.iter().map(|x| vec![format!("part {x}")]))
+            .collect();
+        let rows: Vec<Vec<Vec<String>>> = self
+            .rows
+            .iter()
+            .map(|(set, outcomes)| {
+                std::iter::once(vec![set.clone()])
+                    .chain(outcomes.iter().map(|outcome| match outcome {
+                        Ok(answer) => answer.to_string().lines().map(str::to_string).collect(),
+                        Err(e) => vec![format!("error: {e}")],
+                    }))
+                    .collect()
+            })
+            .collect();
+        let widths: Vec<usize> = (0..header.len())
+            .map(|column| {
+                std::iter::once(&header)
+                    .chain(rows.iter())
+                    .flat_map(|row| row[column].iter())
+                    .map(|line| line.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let mut lines = vec![format!("day {}", self.day).bold().to_string()];
+        for (idx, row) in std::iter::once(&header).chain(rows.iter()).enumerate() {
+            let height = row.iter().map(|x| x.len()).max().unwrap_or(1).max(1);
+            for line in 0..height {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(widths.iter())
+                    .map(|(cell, width)| {
+                        let text = cell.get(line).map(String::as_str).unwrap_or_default();
+                        let padded = format!("{text:<width$}");
+                        match idx {
+                            0 => padded.bold().to_string(),
+                            _ if text.starts_with("error: ") => padded.red().to_string(),
+                            _ => padded,
+                        }
+                    })
+                    .collect();
+                lines.push(cells.join("  ").trim_end().to_string());
+            }
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, path::PathBuf};
+
+    use crate::{
+        input::{NotFound, Source},
+        registry::find,
+        solution::Answer,
+    };
+
+    use super::{reason, Batch};
+
+    #[test]
+    fn table() {
+        colored::control::set_override(false);
+        let inputs = vec![
+            ("alice".to_string(), Ok("A Y\nB X\nC Z".to_string())),
+            ("bob".to_string(), Ok("A X\nA Y".to_string())),
+            ("carol".to_string(), Ok("A W".to_string())),
+            (
+                "dave".to_string(),
+                Err(anyhow::anyhow!("cannot read input")),
+            ),
+        ];
+        let batch = Batch::run(find(2).unwrap(), inputs);
+        assert_eq!(batch.rows[0].1[0], Ok(Answer::Number(15)));
+        assert_eq!(batch.rows[0].1[1], Ok(Answer::Number(12)));
+        assert_eq!(
+            batch.to_string(),
+            "day 2
+set    part 1                                     part 2
+alice  15                                         12
+bob    12                                         7
+carol  error: line 1, column 3: unknown code `W`  error: line 1, column 3: unknown code `W`
+dave   error: cannot read input                   error: cannot read input"
+        );
+    }
+
+    #[test]
+    fn reasons() {
+        let not_found = |kind: io::ErrorKind| {
+            anyhow::Error::from(NotFound {
+                day: 2,
+                tried: vec![
+                    (Source::Path(PathBuf::from("day.2.txt")), kind.into()),
+                    (
+                        Source::Path(PathBuf::from("inputs/2022/day2.txt")),
+                        io::ErrorKind::NotFound.into(),
+                    ),
+                ],
+            })
+        };
+        assert_eq!(reason(&not_found(io::ErrorKind::NotFound)), "no input");
+        assert_eq!(
+            reason(&not_found(io::ErrorKind::PermissionDenied)),
+            "unreadable input"
+        );
+    }
+
+    #[test]
+    fn multiline() {
+        colored::control::set_override(false);
+        let batch = Batch {
+            day: 10,
+            parts: vec![1, 2],
+            rows: vec![(
+                "alice".into(),
+                vec![Ok(Answer::Number(13)), Ok(Answer::Text("#.\n.#".into()))],
+            )],
+        };
+        assert_eq!(
+            batch.to_string(),
+            "day 10\nset    part 1  part 2\nalice  13      #.\n               .#"
+        );
+    }
+}
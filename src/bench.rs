@@ -0,0 +1,122 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use serde::{Serialize, Serializer};
+
+use crate::registry::Day;
+
+fn millis<S: Serializer>(v: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(v.as_secs_f64() * 1_000.)
+}
+
+/// timings of a phase over several iterations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Stats {
+    #[serde(rename = "mean_ms", serialize_with = "millis")]
+    pub mean: Duration,
+    #[serde(rename = "min_ms", serialize_with = "millis")]
+    pub min: Duration,
+    #[serde(rename = "max_ms", serialize_with = "millis")]
+    pub max: Duration,
+}
+
+impl From<&[Duration]> for Stats {
+    fn from(samples: &[Duration]) -> Self {
+        let total: Duration = samples.iter().sum();
+        Self {
+            mean: total / samples.len().max(1) as u32,
+            min: samples.iter().min().copied().unwrap_or_default(),
+            max: samples.iter().max().copied().unwrap_or_default(),
+        }
+    }
+}
+
+/// parse and solve timings of one part
+#[derive(Debug, Clone, Serialize)]
+pub struct Bench {
+    pub day: u8,
+    pub part: u8,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+impl Bench {
+    /// parses then solves `input` for each iteration, timing both phases
+    pub fn run(day: &Day, part: u8, input: &str, iterations: usize) -> anyhow::Result<Self> {
+        let mut parse = Vec::with_capacity(iterations);
+        let mut solve = Vec::with_capacity(iterations);
+        for _ in 0..iterations.max(1) {
+            let start = Instant::now();
+            let parsed = day.parse(input)?;
+            parse.push(start.elapsed());
+            let start = Instant::now();
+            day.solve(part, parsed)?;
+            solve.push(start.elapsed());
+        }
+        Ok(Self {
+            day: day.number,
+            part,
+            iterations: parse.len(),
+            parse: Stats::from(parse.as_slice()),
+            solve: Stats::from(solve.as_slice()),
+        })
+    }
+}
+
+impl Display for Bench {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ms = |x: Duration| x.as_secs_f64() * 1_000.;
+        for (idx, (phase, stats)) in [("parse", self.parse), ("solve", self.solve)]
+            .into_iter()
+            .enumerate()
+        {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            write!(
+                f,
+                "{:>3}  {:>4}  {phase}  {:>10.3}ms  {:>10.3}ms  {:>10.3}ms",
+                self.day,
+                self.part,
+                ms(stats.mean),
+                ms(stats.min),
+                ms(stats.max)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::registry::find;
+
+    use super::{Bench, Stats};
+
+    #[test]
+    fn stats() {
+        let samples = [3, 1, 2].map(Duration::from_millis);
+        let stats = Stats::from(samples.as_slice());
+        assert_eq!(stats.mean, Duration::from_millis(2));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.max, Duration::from_millis(3));
+        assert_eq!(Stats::from([].as_slice()).mean, Duration::ZERO);
+    }
+
+    #[test]
+    fn json() {
+        let bench = Bench::run(find(1).unwrap(), 2, "1000\n\n2000\n\n3000", 3).unwrap();
+        assert_eq!(bench.iterations, 3);
+        assert!(bench.parse.min <= bench.parse.mean && bench.parse.mean <= bench.parse.max);
+        let json = serde_json::to_value(&bench).unwrap();
+        assert_eq!(json["day"], 1);
+        assert_eq!(json["part"], 2);
+        assert!(json["solve"]["mean_ms"].is_f64());
+        assert!(Bench::run(find(1).unwrap(), 1, "abc", 3).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+/// circular list where each value keeps the id it was given at first, its index in the original order,
+/// split into blocks of about sqrt(n) ids so that moving a value costs O(sqrt n)
+#[derive(Debug, Clone)]
+pub struct Circular<T> {
+    values: Vec<T>,
+    /// ids in their current order, block after block
+    blocks: Vec<Vec<usize>>,
+    /// block holding each id
+    block_of: Vec<usize>,
+    /// length of the blocks when last rebuilt
+    size: usize,
+}
+
+impl<T> Circular<T> {
+    pub fn new(values: Vec<T>) -> Self {
+        let size = (values.len() as f64).sqrt().ceil().max(1.) as usize;
+        let mut list = Self {
+            blocks: vec![(0..values.len()).collect()],
+            block_of: vec![0; values.len()],
+            values,
+            size,
+        };
+        list.rebuild();
+        list
+    }
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+    /// value given id `id`
+    pub fn get(&self, id: usize) -> &T {
+        &self.values[id]
+    }
+    /// values in the original order, indexed by id
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+    /// splits the ids back into blocks of the same length
+    fn rebuild(&mut self) {
+        let ids: Vec<usize> = self.blocks.drain(..).flatten().collect();
+        self.blocks = ids.chunks(self.size).map(<[usize]>::to_vec).collect();
+        for (idx, block) in self.blocks.iter().enumerate() {
+            for id in block {
+                self.block_of[*id] = idx;
+            }
+        }
+    }
+    /// current position of `id`, counting from the first block
+    pub fn position(&self, id: usize) -> usize {
+        let block = self.block_of[id];
+        let before: usize = self.blocks[..block].iter().map(Vec::len).sum();
+        before
+            + self.blocks[block]
+                .iter()
+                .position(|x| *x == id)
+                .expect("id in its block")
+    }
+    /// id at `position`, wrapping around
+    pub fn at(&self, position: usize) -> usize {
+        let mut position = position % self.len();
+        for block in self.blocks.iter() {
+            match block.get(position) {
+                Some(id) => return *id,
+                None => position -= block.len(),
+            }
+        }
+        unreachable!("position within the list")
+    }
+    /// value `offset` positions after the one with id `id`, wrapping around
+    pub fn after(&self, id: usize, offset: usize) -> &T {
+        self.get(self.at(self.position(id) + offset))
+    }
+    /// moves `id` by `steps` positions, forward when positive, around the other values
+    pub fn shift(&mut self, id: usize, steps: i64) {
+        if self.len() < 2 {
+            return;
+        }
+        let from = self.position(id);
+        let block = self.block_of[id];
+        self.blocks[block].retain(|x| *x != id);
+        let mut to = (from as i64 + steps).rem_euclid(self.len() as i64 - 1) as usize;
+        for (idx, block) in self.blocks.iter_mut().enumerate() {
+            if to <= block.len() {
+                block.insert(to, id);
+                self.block_of[id] = idx;
+                if block.len() > 2 * self.size {
+                    self.rebuild();
+                }
+                return;
+            }
+            to -= block.len();
+        }
+        unreachable!("position within the list")
+    }
+    /// values in their current order, starting from the first block
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.blocks.iter().flatten().map(|x| &self.values[*x])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Circular;
+
+    /// same moves on a plain vector
+    fn naive(values: &[i64], order: &[usize]) -> Vec<i64> {
+        let mut ids: Vec<usize> = (0..values.len()).collect();
+        for id in order {
+            let from = ids.iter().position(|x| x == id).unwrap();
+            ids.remove(from);
+            let to = (from as i64 + values[*id]).rem_euclid(values.len() as i64 - 1) as usize;
+            ids.insert(to, *id);
+        }
+        ids.into_iter().map(|x| values[x]).collect()
+    }
+
+    #[test]
+    fn shift() {
+        let mut list = Circular::new(vec![1, 2, -3, 3, -2, 0, 4]);
+        list.shift(0, 1);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            [2, 1, -3, 3, -2, 0, 4]
+        );
+        list.shift(2, -3);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            [2, 1, 3, -2, 0, -3, 4]
+        );
+        assert_eq!(list.position(2), 5);
+        assert_eq!(*list.after(5, 1), -3);
+        assert_eq!(*list.after(5, 3), 2);
+        let mut single = Circular::new(vec![7]);
+        single.shift(0, 5);
+        assert_eq!(*single.after(0, 10), 7);
+    }
+
+    #[test]
+    fn blocks() {
+        let values: Vec<i64> = (0..200).map(|x| (x * 7919) % 401 - 200).collect();
+        let order: Vec<usize> = (0..values.len()).chain((0..values.len()).rev()).collect();
+        let mut list = Circular::new(values.clone());
+        for id in order.iter() {
+            list.shift(*id, values[*id]);
+        }
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            naive(&values, &order)
+        );
+        assert_eq!(list.values(), values.as_slice());
+    }
+}
@@ -0,0 +1,138 @@
+use std::{collections::HashMap, hash::Hash};
+
+use num_traits::PrimInt;
+
+/// states from `start` on repeat every `length` steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// step before the end of the first cycle that leads to the same state as step `n`
+    pub fn equivalent(&self, n: usize) -> usize {
+        match n < self.start {
+            true => n,
+            false => self.start + (n - self.start) % self.length,
+        }
+    }
+    /// number of whole cycles between the equivalent step and step `n`
+    pub fn laps(&self, n: usize) -> usize {
+        n.saturating_sub(self.start) / self.length
+    }
+    /// value after `n` steps of a quantity growing by the same amount every cycle,
+    /// from its values after each step so far, `history[0]` being before any step
+    pub fn extrapolate<T: PrimInt>(&self, history: &[T], n: usize) -> T {
+        let end = self.start + self.length;
+        let gain = history[end] - history[self.start];
+        let laps = T::from(self.laps(n)).expect("laps to fit");
+        history[self.equivalent(n)] + gain * laps
+    }
+}
+
+/// remembers the states it observes, one per step, until one comes back
+#[derive(Debug, Clone)]
+pub struct Detector<K> {
+    seen: HashMap<K, usize>,
+    steps: usize,
+}
+
+impl<K: Eq + Hash> Detector<K> {
+    pub fn new() -> Self {
+        Self {
+            seen: HashMap::new(),
+            steps: 0,
+        }
+    }
+    /// records `key` as the next state, returning the cycle if it was already seen
+    pub fn observe(&mut self, key: K) -> Option<Cycle> {
+        let step = self.steps;
+        self.steps += 1;
+        self.seen.insert(key, step).map(|start| Cycle {
+            start,
+            length: step - start,
+        })
+    }
+}
+
+impl<K: Eq + Hash> Default for Detector<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// value of `measure` after `n` calls to `step`, skipping whole cycles once the `key` of `state` repeats
+pub fn simulate<S, K, T>(
+    state: &mut S,
+    n: usize,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+    mut measure: impl FnMut(&S) -> T,
+) -> T
+where
+    K: Eq + Hash,
+    T: PrimInt,
+{
+    let mut detector = Detector::new();
+    let mut history = vec![measure(state)];
+    for _ in 0..n {
+        if let Some(cycle) = detector.observe(key(state)) {
+            return cycle.extrapolate(&history, n);
+        }
+        step(state);
+        history.push(measure(state));
+    }
+    history[n]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{simulate, Cycle, Detector};
+
+    #[test]
+    fn detect() {
+        let mut detector = Detector::new();
+        let found: Vec<Option<Cycle>> = [3, 1, 4, 1, 5]
+            .into_iter()
+            .map(|x| detector.observe(x))
+            .collect();
+        assert_eq!(found[..3], [None, None, None]);
+        assert_eq!(
+            found[3],
+            Some(Cycle {
+                start: 1,
+                length: 2
+            })
+        );
+        assert_eq!(found[4], None);
+        let cycle = Cycle {
+            start: 2,
+            length: 3,
+        };
+        assert_eq!((cycle.equivalent(1), cycle.laps(1)), (1, 0));
+        assert_eq!((cycle.equivalent(4), cycle.laps(4)), (4, 0));
+        assert_eq!((cycle.equivalent(5), cycle.laps(5)), (2, 1));
+        assert_eq!((cycle.equivalent(12), cycle.laps(12)), (3, 3));
+    }
+
+    #[test]
+    fn skip() {
+        // counts up to 7 then back to 4, forever, while `total` keeps adding the counter
+        let step = |(counter, total): &mut (u64, u64)| {
+            *counter = if *counter == 7 { 4 } else { *counter + 1 };
+            *total += *counter;
+        };
+        let brute = |n: usize| {
+            let mut state = (0, 0);
+            (0..n).for_each(|_| step(&mut state));
+            state.1
+        };
+        for n in [0, 3, 7, 8, 9, 10, 11, 100, 1001] {
+            let skipped = simulate(&mut (0, 0), n, step, |x| x.0, |x| x.1);
+            assert_eq!(skipped, brute(n), "after {n} steps");
+        }
+        let skipped = simulate(&mut (0, 0), 1_000_000_000_000, step, |x| x.0, |x| x.1);
+        assert_eq!(skipped, 5_499_999_999_988);
+    }
+}
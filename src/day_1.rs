@@ -1,3 +1,11 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    diagnostics::Grammar,
+    parse::{ParseError, Span},
+    solution::Solution,
+};
+
 #[derive(Debug, Clone)]
 pub struct Supply(Vec<u32>);
 
@@ -45,12 +53,13 @@ impl Calories for Vec<u32> {
 }
 
 impl TopCalories for Supplies {
+    /// calories of the `times` elves carrying the most, or of every elf if there are fewer
     fn top_calories(&self, times: usize) -> Vec<u32> {
         let mut inner = self.0.clone();
         let mut calories = Vec::with_capacity(times);
         let mut v;
         let mut idx;
-        for _ in 0..times {
+        for _ in 0..times.min(self.0.len()) {
             v = inner.calories();
             calories.push(v);
             idx = inner
@@ -58,7 +67,7 @@ impl TopCalories for Supplies {
                 .enumerate()
                 .find_map(|(index, item)| {
                     if item.calories() == v {
-                        return Some(index);
+                        Some(index)
                     } else {
                         None
                     }
@@ -70,20 +79,72 @@ impl TopCalories for Supplies {
     }
 }
 
-impl From<&str> for Supplies {
-    fn from(v: &str) -> Self {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuppliesError {
+    InvalidCalories(String),
+}
+
+impl Display for SuppliesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidCalories(v) => write!(f, "expected calories, found `{v}`"),
+        }
+    }
+}
+
+impl Grammar for SuppliesError {
+    fn expected(&self) -> &'static str {
+        "<calories>, one per line, with a blank line between elves"
+    }
+    fn hint(&self) -> Option<String> {
+        Some("calories are whole numbers, e.g. `1000`".into())
+    }
+}
+
+impl FromStr for Supplies {
+    type Err = ParseError<SuppliesError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
         let mut supplies = vec![];
         let mut supply = vec![];
-        for line in v.lines() {
-            if !line.trim().is_empty() {
-                supply.push(line.trim().parse().expect("should be a digit"));
+        for (idx, line) in v.lines().enumerate() {
+            let calories = line.trim();
+            if !calories.is_empty() {
+                supply.push(calories.parse().map_err(|_| {
+                    ParseError::new(
+                        SuppliesError::InvalidCalories(calories.to_string()),
+                        Span::of(line, calories),
+                    )
+                    .shift(idx)
+                })?);
             } else {
                 supplies.push(Supply(supply.clone()));
                 supply.clear();
             }
         }
         supplies.push(Supply(supply.clone()));
-        Supplies(supplies)
+        Ok(Supplies(supplies))
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Calorie Counting";
+
+    type Parsed = Supplies;
+    type Error = ParseError<SuppliesError>;
+    type One = u32;
+    type Two = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(supplies: Self::Parsed) -> Self::One {
+        supplies.calories()
+    }
+    fn part_two(supplies: Self::Parsed) -> Self::Two {
+        supplies.top_calories(3).calories()
     }
 }
 
@@ -91,7 +152,9 @@ impl From<&str> for Supplies {
 mod tests {
     use crate::day_1::TopCalories;
 
-    use super::{Calories, Supplies};
+    use crate::parse::Span;
+
+    use super::{Calories, Supplies, SuppliesError};
 
     #[test]
     fn convert() {
@@ -109,10 +172,10 @@ mod tests {
       9000
       
       10000";
-        let supplies = Supplies::from(s);
+        let supplies: Supplies = s.parse().unwrap();
         println!("{supplies:#?}");
         assert_eq!(supplies.0.len(), 5);
-        assert_eq!(supplies.0.get(0).unwrap().calories(), 6_000);
+        assert_eq!(supplies.0.first().unwrap().calories(), 6_000);
         assert_eq!(supplies.0.get(1).unwrap().calories(), 4_000);
         assert_eq!(supplies.0.get(2).unwrap().calories(), 11_000);
         assert_eq!(supplies.0.get(3).unwrap().calories(), 24_000);
@@ -120,5 +183,14 @@ mod tests {
         assert_eq!(supplies.calories(), 24_000);
         assert_eq!(supplies.top_calories(3), vec![24_000, 11_000, 10_000]);
         assert_eq!(supplies.top_calories(3).calories(), 45_000);
+        let few: Supplies = "1000\n\n2000".parse().unwrap();
+        assert_eq!(few.top_calories(3), vec![2_000, 1_000]);
+    }
+
+    #[test]
+    fn invalid() {
+        let err = "1000\n\n  20o0".parse::<Supplies>().unwrap_err();
+        assert_eq!(err.kind, SuppliesError::InvalidCalories("20o0".into()));
+        assert_eq!(err.span, Span::new(3, 3, 4));
     }
 }
@@ -1,6 +1,13 @@
-#![allow(dead_code)]
+use std::{collections::VecDeque, fmt::Display, str::FromStr};
 
-use std::collections::VecDeque;
+use crate::{
+    diagnostics::Grammar,
+    geometry::Point,
+    grid::Grid,
+    parse::{self, ParseError, Span},
+    picture::Picture,
+    solution::Solution,
+};
 
 pub trait Cadence: Sized {
     fn start(&mut self);
@@ -9,10 +16,10 @@ pub trait Cadence: Sized {
 }
 
 pub trait Draw {
-    fn draw(&mut self, cpu: &CPU, cycle: &Cycle);
+    fn draw(&mut self, cpu: &Cpu, cycle: &Cycle);
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Pixel(u8);
 
 impl Pixel {
@@ -26,14 +33,14 @@ impl Pixel {
 
 pub struct Sprite {
     cycle: Cycle,
-    cpu: CPU,
+    cpu: Cpu,
 }
 
-impl From<(&Cycle, &CPU)> for Sprite {
-    fn from((cycle, cpu): (&Cycle, &CPU)) -> Self {
+impl From<(&Cycle, &Cpu)> for Sprite {
+    fn from((cycle, cpu): (&Cycle, &Cpu)) -> Self {
         Self {
-            cycle: cycle.clone(),
-            cpu: cpu.clone(),
+            cycle: *cycle,
+            cpu: *cpu,
         }
     }
 }
@@ -58,23 +65,37 @@ impl Sprite {
     }
 }
 
+impl Display for Pixel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Pixel(0) => write!(f, "."),
+            Pixel(1) => write!(f, "#"),
+            _ => panic!("should not happen"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
-pub struct CRT([[Pixel; 40]; 6]);
+pub struct Crt(Grid<Pixel>);
 
-impl Default for CRT {
+impl Default for Crt {
     fn default() -> Self {
-        Self([[Pixel::low(); 40]; 6])
+        Self(Grid::new(40, 6, Pixel::low()))
     }
 }
 
-impl Draw for CRT {
-    fn draw(&mut self, cpu: &CPU, cycle: &Cycle) {
+impl Draw for Crt {
+    fn draw(&mut self, cpu: &Cpu, cycle: &Cycle) {
         let sprite: Sprite = (cycle, cpu).into();
-        self.0[sprite.column()][cycle.pixel_row_position()] = sprite.produce();
+        let at = Point::new(
+            cycle.pixel_row_position() as isize,
+            sprite.column() as isize,
+        );
+        self.0[at] = sprite.produce();
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct Cycle(usize);
 
 impl Cycle {
@@ -92,8 +113,9 @@ impl Cycle {
 impl Cadence for Clock {
     fn start(&mut self) {
         if self.busy == 0 {
+            // once the program runs out, the cpu idles until the screen is drawn
             self.current = self.instructions.0.pop_front();
-            self.busy = self.current.as_ref().unwrap().as_cycles();
+            self.busy = self.current.as_ref().map_or(0, Instruction::as_cycles);
         }
     }
 
@@ -109,30 +131,27 @@ impl Cadence for Clock {
             self.busy -= 1;
         }
         if self.busy == 0 {
-            match self.current {
-                Some(Instruction::AddX(v)) => {
-                    self.cpu.0 += v;
-                }
-                _ => {}
+            if let Some(Instruction::AddX(v)) = self.current {
+                self.cpu.0 += v;
             };
         }
     }
 }
 
 impl Draw for Clock {
-    fn draw(&mut self, cpu: &CPU, cycle: &Cycle) {
+    fn draw(&mut self, cpu: &Cpu, cycle: &Cycle) {
         self.crt.draw(cpu, cycle);
     }
 }
 
 pub struct Clock {
-    cpu: CPU,
+    cpu: Cpu,
     cycle: Cycle,
-    crt: CRT,
+    crt: Crt,
     busy: usize,
     current: Option<Instruction>,
     instructions: Instructions,
-    signals: Vec<(Cycle, CPU)>,
+    signals: Vec<(Cycle, Cpu)>,
 }
 
 impl Clock {
@@ -164,36 +183,19 @@ impl Clock {
     }
 
     pub fn draw_picture(&self) -> String {
-        let mut buf = String::from("");
-        for line in self.crt.0.iter() {
-            for pixel in line.iter() {
-                match pixel {
-                    &Pixel(0) => buf.push_str("."),
-                    &Pixel(1) => buf.push_str("#"),
-                    _ => panic!("should not happen"),
-                };
-            }
-            buf.push_str("\n");
-        }
-        buf
+        self.crt.0.to_string()
     }
 }
 
 #[derive(Debug, Clone, Copy)]
-pub struct CPU(isize);
+pub struct Cpu(isize);
 
-impl Default for CPU {
+impl Default for Cpu {
     fn default() -> Self {
         Self(1)
     }
 }
 
-impl Default for Cycle {
-    fn default() -> Self {
-        Self(0)
-    }
-}
-
 #[derive(Debug)]
 pub enum Instruction {
     AddX(isize),
@@ -209,24 +211,333 @@ impl Instruction {
     }
 }
 
+#[derive(Debug)]
 pub struct Instructions(pub VecDeque<Instruction>);
 
-impl From<&str> for Instruction {
-    fn from(v: &str) -> Self {
-        match &v[0..4] {
-            "addx" => Self::AddX(v[5..].parse().expect("should be a digit")),
-            "noop" => Self::NoOp,
-            _ => panic!("unknown instruction"),
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstructionError {
+    UnknownInstruction(String),
+    InvalidValue(String),
+    MissingValue,
+    TrailingInput(String),
+}
+
+impl Display for InstructionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownInstruction(v) => write!(f, "unknown instruction `{v}`"),
+            Self::InvalidValue(v) => write!(f, "expected an integer, found `{v}`"),
+            Self::MissingValue => write!(f, "expected an integer"),
+            Self::TrailingInput(v) => write!(f, "unexpected `{v}` after the instruction"),
         }
     }
 }
 
-impl From<&str> for Instructions {
-    fn from(v: &str) -> Self {
-        let mut instructions = VecDeque::new();
-        for line in v.lines() {
-            instructions.push_back(Instruction::from(line));
+impl Grammar for InstructionError {
+    fn expected(&self) -> &'static str {
+        "addx <int> | noop"
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError<InstructionError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let mut parts = v.split_whitespace();
+        let name = parts.next().unwrap_or_default();
+        let instruction = match name {
+            "addx" => {
+                let value = parts.next().ok_or_else(|| {
+                    ParseError::new(InstructionError::MissingValue, Span::after(v))
+                })?;
+                Self::AddX(value.parse().map_err(|_| {
+                    ParseError::new(
+                        InstructionError::InvalidValue(value.to_string()),
+                        Span::of(v, value),
+                    )
+                })?)
+            }
+            "noop" => Self::NoOp,
+            _ => {
+                return Err(ParseError::new(
+                    InstructionError::UnknownInstruction(name.to_string()),
+                    Span::of(v, name),
+                ))
+            }
+        };
+        match parts.next() {
+            Some(extra) => Err(ParseError::new(
+                InstructionError::TrailingInput(extra.to_string()),
+                Span::of(v, extra),
+            )),
+            None => Ok(instruction),
         }
-        Self(instructions)
+    }
+}
+
+impl FromStr for Instructions {
+    type Err = ParseError<InstructionError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        Ok(Self(parse::lines(v)?.into()))
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Cathode-Ray Tube";
+
+    type Parsed = Instructions;
+    type Error = ParseError<InstructionError>;
+    type One = isize;
+    type Two = Picture;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(instructions: Self::Parsed) -> Self::One {
+        let mut clock = Clock::new(instructions);
+        clock.execute();
+        clock.total_signals_strength()
+    }
+    fn part_two(instructions: Self::Parsed) -> Self::Two {
+        let mut clock = Clock::new(instructions);
+        clock.execute();
+        Picture::from(clock.draw_picture().as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse::Span, solution::Solution};
+
+    use super::{Clock, Instruction, InstructionError, Instructions, Puzzle};
+
+    const INPUT: &str = "noop
+addx 3
+addx -5";
+
+    const LARGER_INPUT: &str = "addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop";
+
+    #[test]
+    fn parse() {
+        let instructions = INPUT.parse::<Instructions>().unwrap();
+        assert_eq!(instructions.0.len(), 3);
+    }
+
+    #[test]
+    fn signals() {
+        let instructions = LARGER_INPUT.parse::<Instructions>().unwrap();
+        assert_eq!(instructions.0.len(), 146);
+
+        let mut clock = Clock::new(instructions);
+        clock.execute();
+        assert_eq!(&clock.total_signals_strength(), &13140);
+    }
+
+    const EXPECT: &str = "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+
+    #[test]
+    fn picture() {
+        let instructions = LARGER_INPUT.parse::<Instructions>().unwrap();
+        assert_eq!(instructions.0.len(), 146);
+
+        let mut clock = Clock::new(instructions);
+        clock.execute();
+        assert_eq!(
+            &clock.draw_picture().replace("\n", ""),
+            &EXPECT.replace("\n", "")
+        );
+    }
+
+    #[test]
+    fn idle() {
+        let mut clock = Clock::new(INPUT.parse::<Instructions>().unwrap());
+        clock.execute();
+        assert_eq!(clock.total_signals_strength(), -720);
+    }
+
+    #[test]
+    fn solution() {
+        assert_eq!(
+            Puzzle::part_one(Puzzle::parse(LARGER_INPUT).unwrap()),
+            13140
+        );
+        assert_eq!(
+            Puzzle::part_two(Puzzle::parse(LARGER_INPUT).unwrap()).to_string(),
+            EXPECT
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT
+            .replace("addx 3", "addy 3")
+            .parse::<Instructions>()
+            .unwrap_err();
+        assert_eq!(
+            err.kind,
+            InstructionError::UnknownInstruction("addy".into())
+        );
+        assert_eq!(err.span, Span::new(2, 1, 4));
+        let err = INPUT
+            .replace("-5", "-S")
+            .parse::<Instructions>()
+            .unwrap_err();
+        assert_eq!(err.kind, InstructionError::InvalidValue("-S".into()));
+        assert_eq!(err.span, Span::new(3, 6, 2));
+        let err = "addx".parse::<Instruction>().unwrap_err();
+        assert_eq!(err.kind, InstructionError::MissingValue);
+        assert_eq!(err.span, Span::new(1, 5, 1));
     }
 }
@@ -1,12 +1,16 @@
-#![allow(dead_code, unused_variables)]
+use std::{collections::VecDeque, fmt::Display, str::FromStr};
 
-use std::collections::VecDeque;
+use crate::{
+    diagnostics::Grammar,
+    parse::{ParseError, Span},
+    solution::Solution,
+};
 
 pub struct Myself;
 
 impl Myself {
     pub fn observe(monkeys: &mut Monkeys, rounds: usize, relief: bool) {
-        for round in 1..=rounds {
+        for _ in 0..rounds {
             let divisibles: usize = if relief {
                 3
             } else {
@@ -15,7 +19,6 @@ impl Myself {
             for turn in 0..monkeys.0.len() {
                 let thrower = monkeys.0.get_mut(turn).expect("thrower");
                 let outcomes = thrower.turn(relief, divisibles);
-                drop(thrower);
                 for (item, decision) in outcomes {
                     monkeys.throw(item, decision.recipient);
                 }
@@ -34,6 +37,9 @@ pub struct Monkey {
 }
 
 impl Monkey {
+    pub fn idx(&self) -> usize {
+        self.idx
+    }
     fn turn(&mut self, relief: bool, divisibles: usize) -> Vec<(Item, Decision)> {
         let mut decisions = vec![];
         while let Some(mut item) = self.starting_items.0.pop_front() {
@@ -48,10 +54,10 @@ impl Monkey {
         self.inspected += 1;
     }
     fn test(&self, item: &Item) -> Decision {
-        if item.0 % self.test.divisible == 0 {
-            return self.test.if_true.clone();
+        if item.0.is_multiple_of(self.test.divisible) {
+            return self.test.if_true;
         }
-        self.test.if_false.clone()
+        self.test.if_false
     }
 }
 
@@ -63,6 +69,12 @@ impl Monkeys {
         let catcher = self.0.get_mut(to).expect("catcher");
         catcher.starting_items.0.push_back(item);
     }
+    /// product of the two most active monkeys' inspections
+    pub fn business(&self) -> usize {
+        let mut inspected: Vec<usize> = self.0.iter().map(|x| x.inspected).collect();
+        inspected.sort_by_key(|x| std::cmp::Reverse(*x));
+        inspected.iter().take(2).product()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -71,9 +83,9 @@ pub struct Item(usize);
 impl Item {
     fn relief(&mut self, relief: bool, divisibles: usize) {
         if relief {
-            self.0 = self.0 / divisibles;
+            self.0 /= divisibles;
         } else {
-            self.0 = self.0 % divisibles;
+            self.0 %= divisibles;
         }
     }
 }
@@ -106,8 +118,8 @@ impl Operation {
             Operand::Old => item.0,
         };
         match self.operator {
-            Operator::Plus => item.0 = item.0 + operand,
-            Operator::Times => item.0 = item.0 * operand,
+            Operator::Plus => item.0 += operand,
+            Operator::Times => item.0 *= operand,
         };
     }
 }
@@ -115,6 +127,7 @@ impl Operation {
 #[derive(Debug, Clone, Copy)]
 pub struct Decision {
     recipient: usize,
+    span: Span,
 }
 
 #[derive(Debug)]
@@ -124,119 +137,238 @@ pub struct Test {
     if_false: Decision,
 }
 
-impl From<&str> for StartingItems {
-    fn from(v: &str) -> Self {
-        Self(
-            v["Starting items: ".len()..]
-                .split(", ")
-                .map(|x| Item(x.parse().expect("should be a digit")))
-                .collect(),
-        )
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MonkeyError {
+    Expected(&'static str),
+    InvalidNumber(String),
+    UnknownOperator(String),
+    UnknownMonkey(usize),
+    TrailingInput(String),
+}
+
+impl Display for MonkeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Expected(v) => write!(f, "expected `{v}`"),
+            Self::InvalidNumber(v) => write!(f, "expected a number, found `{v}`"),
+            Self::UnknownOperator(v) => write!(f, "expected `+` or `*`, found `{v}`"),
+            Self::UnknownMonkey(v) => write!(f, "there is no monkey {v}"),
+            Self::TrailingInput(v) => write!(f, "unexpected `{v}`"),
+        }
     }
 }
 
-impl From<&str> for Operator {
-    fn from(v: &str) -> Self {
-        match v {
-            "+" => Operator::Plus,
-            "*" => Operator::Times,
-            _ => panic!("should not happen"),
+impl Grammar for MonkeyError {
+    fn expected(&self) -> &'static str {
+        "Monkey <n>:
+  Starting items: <n>, ...
+  Operation: new = old <+|*> <old|n>
+  Test: divisible by <n>
+    If true: throw to monkey <n>
+    If false: throw to monkey <n>"
+    }
+    fn hint(&self) -> Option<String> {
+        match self {
+            Self::UnknownMonkey(_) => Some("monkeys are numbered from 0".into()),
+            _ => None,
         }
     }
 }
 
-impl From<&str> for Operand {
-    fn from(v: &str) -> Self {
-        if v == "old" {
-            return Self::Old;
+/// remainder of `line` after `label`, ignoring indentation
+fn field<'a>(line: &'a str, label: &'static str) -> Result<&'a str, ParseError<MonkeyError>> {
+    line.trim()
+        .strip_prefix(label)
+        .map(str::trim)
+        .ok_or_else(|| ParseError::new(MonkeyError::Expected(label), Span::of(line, line.trim())))
+}
+
+fn number(line: &str, token: &str) -> Result<usize, ParseError<MonkeyError>> {
+    token.parse().map_err(|_| {
+        ParseError::new(
+            MonkeyError::InvalidNumber(token.to_string()),
+            Span::of(line, token),
+        )
+    })
+}
+
+impl FromStr for StartingItems {
+    type Err = ParseError<MonkeyError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        field(v, "Starting items:")?
+            .split(',')
+            .map(|x| number(v, x.trim()).map(Item))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl FromStr for Operator {
+    type Err = MonkeyError;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        match v {
+            "+" => Ok(Operator::Plus),
+            "*" => Ok(Operator::Times),
+            _ => Err(MonkeyError::UnknownOperator(v.to_string())),
         }
-        Self::Digit(v.parse().expect("should be a digit"))
     }
 }
 
-impl From<&str> for Operation {
-    fn from(v: &str) -> Self {
-        let parts: Vec<&str> = v.split(' ').collect();
-        assert_eq!(parts.len(), 6);
-        let operator: Operator = Operator::from(*parts.get(4).expect("operator"));
-        let operand = Operand::from(*parts.get(5).expect("operand"));
-        Self { operator, operand }
+impl FromStr for Operation {
+    type Err = ParseError<MonkeyError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let expression = field(v, "Operation: new = old")?;
+        let mut parts = expression.split_whitespace();
+        let (Some(operator), Some(operand)) = (parts.next(), parts.next()) else {
+            return Err(ParseError::new(
+                MonkeyError::Expected("<+|*> <old|n>"),
+                Span::after(v),
+            ));
+        };
+        if let Some(extra) = parts.next() {
+            return Err(ParseError::new(
+                MonkeyError::TrailingInput(extra.to_string()),
+                Span::of(v, extra),
+            ));
+        }
+        let operator = operator
+            .parse()
+            .map_err(|e| ParseError::new(e, Span::of(v, operator)))?;
+        let operand = match operand {
+            "old" => Operand::Old,
+            digit => Operand::Digit(number(v, digit)?),
+        };
+        Ok(Self { operator, operand })
     }
 }
 
-impl From<&str> for Decision {
-    fn from(v: &str) -> Self {
-        let recipient = v[v.rfind(' ').expect("last space") + 1..]
-            .parse::<usize>()
-            .expect("should be a digit");
-        Self { recipient }
+impl Decision {
+    fn parse(v: &str, label: &'static str) -> Result<Self, ParseError<MonkeyError>> {
+        let recipient = field(v, label)?;
+        Ok(Self {
+            recipient: number(v, recipient)?,
+            span: Span::of(v, recipient),
+        })
     }
 }
 
-impl From<&str> for Test {
-    fn from(v: &str) -> Self {
-        let lines: Vec<&str> = v.lines().collect();
-        assert_eq!(lines.len(), 3);
-        let line = lines.get(0).expect("divisible");
-        let divisible = *&line[line.rfind(' ').expect("last space") + 1..]
-            .parse::<usize>()
-            .expect("should be a digit");
-        let line = lines.get(1).expect("if true");
-        let if_true = Decision::from(*line);
-        let line = lines.get(2).expect("if false");
-        let if_false = Decision::from(*line);
-        Self {
+impl TryFrom<&[&str]> for Test {
+    type Error = ParseError<MonkeyError>;
+    fn try_from(lines: &[&str]) -> Result<Self, Self::Error> {
+        let [divisible, if_true, if_false] = lines else {
+            return Err(expected_line(lines, "If false: throw to monkey"));
+        };
+        let divisible = number(divisible, field(divisible, "Test: divisible by")?)?;
+        let if_true =
+            Decision::parse(if_true, "If true: throw to monkey").map_err(|e| e.shift(1))?;
+        let if_false =
+            Decision::parse(if_false, "If false: throw to monkey").map_err(|e| e.shift(2))?;
+        Ok(Self {
             divisible,
             if_true,
             if_false,
-        }
+        })
     }
 }
 
-impl From<Vec<&str>> for Monkey {
-    fn from(lines: Vec<&str>) -> Self {
-        assert_eq!(lines.len(), 6);
-        let line = lines.get(0).expect("monkey");
-        let idx = line[line.rfind(' ').unwrap() + 1..line.len() - 1]
-            .parse::<usize>()
-            .expect("should be a digit");
-        let line = lines.get(1).expect("starting items list");
-        let starting_items = StartingItems::from(*line);
-        let line = lines.get(2).expect("operation");
-        let operation = Operation::from(*line);
-        let test = Test::from(lines[3..].join("\n").as_str());
-        Self {
+/// a line is missing at the end of `lines`
+fn expected_line(lines: &[&str], label: &'static str) -> ParseError<MonkeyError> {
+    let last = lines.last().copied().unwrap_or_default();
+    ParseError::new(MonkeyError::Expected(label), Span::after(last))
+        .shift(lines.len().saturating_sub(1))
+}
+
+impl TryFrom<&[&str]> for Monkey {
+    type Error = ParseError<MonkeyError>;
+    fn try_from(lines: &[&str]) -> Result<Self, Self::Error> {
+        let [header, starting_items, operation, test @ ..] = lines else {
+            return Err(expected_line(lines, "Operation: new = old"));
+        };
+        let idx = field(header, "Monkey")?;
+        let idx = idx.strip_suffix(':').ok_or_else(|| {
+            ParseError::new(MonkeyError::Expected(":"), Span::after(header.trim_end()))
+        })?;
+        let idx = number(header, idx)?;
+        let starting_items = starting_items
+            .parse()
+            .map_err(|e: Self::Error| e.shift(1))?;
+        let operation = operation.parse().map_err(|e: Self::Error| e.shift(2))?;
+        let test = Test::try_from(test).map_err(|e| e.shift(3))?;
+        Ok(Self {
             idx,
             starting_items,
             operation,
             test,
             inspected: 0,
-        }
+        })
     }
 }
 
-impl From<&str> for Monkeys {
-    fn from(v: &str) -> Self {
+impl FromStr for Monkeys {
+    type Err = ParseError<MonkeyError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
         let mut monkeys = vec![];
+        let mut starts = vec![];
         let mut acc = Vec::with_capacity(6);
-        for (i, line) in v.lines().map(|x| x.trim()).enumerate() {
-            if !line.is_empty() {
+        let mut start = 0;
+        for (idx, line) in v.lines().chain(std::iter::once("")).enumerate() {
+            if !line.trim().is_empty() {
+                if acc.is_empty() {
+                    start = idx;
+                }
                 acc.push(line);
-            } else {
-                monkeys.push(Monkey::from(acc.clone()));
+            } else if !acc.is_empty() {
+                monkeys.push(Monkey::try_from(acc.as_slice()).map_err(|e| e.shift(start))?);
+                starts.push(start);
                 acc.clear();
             }
         }
-        monkeys.push(Monkey::from(acc.clone()));
-        Self(monkeys)
+        for (monkey, start) in monkeys.iter().zip(starts) {
+            // decisions are on the 5th and 6th lines of each monkey
+            for (decision, line) in [(monkey.test.if_true, 4), (monkey.test.if_false, 5)] {
+                if decision.recipient >= monkeys.len() {
+                    return Err(ParseError::new(
+                        MonkeyError::UnknownMonkey(decision.recipient),
+                        decision.span.shift(start + line),
+                    ));
+                }
+            }
+        }
+        Ok(Self(monkeys))
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Monkey in the Middle";
+
+    type Parsed = Monkeys;
+    type Error = ParseError<MonkeyError>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(mut monkeys: Self::Parsed) -> Self::One {
+        Myself::observe(&mut monkeys, 20, true);
+        monkeys.business()
+    }
+    fn part_two(mut monkeys: Self::Parsed) -> Self::Two {
+        Myself::observe(&mut monkeys, 10_000, false);
+        monkeys.business()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Monkeys;
+    use crate::{parse::Span, solution::Solution};
 
-    const INPUT: &'static str = "  Monkey 0:
+    use super::{MonkeyError, Monkeys, Myself, Puzzle};
+
+    const INPUT: &str = "  Monkey 0:
     Starting items: 79, 98
     Operation: new = old * 19
     Test: divisible by 23
@@ -266,7 +398,63 @@ mod tests {
 
     #[test]
     fn parse() {
-        let monkeys = Monkeys::from(INPUT);
+        let monkeys = INPUT.parse::<Monkeys>().unwrap();
         assert_eq!(monkeys.0.len(), 4);
     }
+
+    #[test]
+    fn basics() {
+        let mut monkeys = INPUT.parse::<Monkeys>().unwrap();
+        Myself::observe(&mut monkeys, 20, true);
+        assert_eq!(monkeys.0.first().unwrap().inspected, 101);
+        assert_eq!(monkeys.0.get(1).unwrap().inspected, 95);
+        assert_eq!(monkeys.0.get(2).unwrap().inspected, 7);
+        assert_eq!(monkeys.0.get(3).unwrap().inspected, 105);
+    }
+
+    #[test]
+    fn no_relief() {
+        let mut monkeys = INPUT.parse::<Monkeys>().unwrap();
+        Myself::observe(&mut monkeys, 10_000, false);
+        assert_eq!(monkeys.0.first().unwrap().inspected, 52166);
+        assert_eq!(monkeys.0.get(1).unwrap().inspected, 47830);
+        assert_eq!(monkeys.0.get(2).unwrap().inspected, 1938);
+        assert_eq!(monkeys.0.get(3).unwrap().inspected, 52013);
+    }
+
+    #[test]
+    fn solution() {
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), 10_605);
+        assert_eq!(
+            Puzzle::part_two(Puzzle::parse(INPUT).unwrap()),
+            2_713_310_158
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT
+            .replace("old + 6", "old - 6")
+            .parse::<Monkeys>()
+            .unwrap_err();
+        assert_eq!(err.kind, MonkeyError::UnknownOperator("-".into()));
+        assert_eq!(err.span, Span::new(10, 26, 1));
+        let err = INPUT
+            .replace("79, 60", "79, sixty")
+            .parse::<Monkeys>()
+            .unwrap_err();
+        assert_eq!(err.kind, MonkeyError::InvalidNumber("sixty".into()));
+        assert_eq!(err.span, Span::new(16, 25, 5));
+        let err = INPUT
+            .replace("monkey 0\n  \n", "monkey 4\n  \n")
+            .parse::<Monkeys>()
+            .unwrap_err();
+        assert_eq!(err.kind, MonkeyError::UnknownMonkey(4));
+        assert_eq!(err.span, Span::new(13, 33, 1));
+        let err = INPUT
+            .replace("    Test: divisible by 17\n", "")
+            .parse::<Monkeys>()
+            .unwrap_err();
+        assert_eq!(err.kind, MonkeyError::Expected("If false: throw to monkey"));
+    }
 }
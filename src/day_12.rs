@@ -0,0 +1,234 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    diagnostics::Grammar,
+    geometry::{Direction, Point},
+    grid::{Grid, GridError},
+    parse::{ParseError, Span},
+    pathfinding::{bfs, Path},
+    solution::{Solution, Unsolvable},
+};
+
+/// elevation of a square, from 0 for `a` to 25 for `z`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Elevation(u8);
+
+impl TryFrom<char> for Elevation {
+    type Error = char;
+    fn try_from(v: char) -> Result<Self, Self::Error> {
+        match v {
+            'S' => Ok(Self(0)),
+            'E' => Ok(Self(25)),
+            'a'..='z' => Ok(Self(v as u8 - b'a')),
+            _ => Err(v),
+        }
+    }
+}
+
+impl Elevation {
+    /// whether one can step from this square onto `to`, climbing at most one level
+    fn reaches(&self, to: &Self) -> bool {
+        to.0 <= self.0 + 1
+    }
+}
+
+#[derive(Debug)]
+pub struct Heightmap {
+    grid: Grid<Elevation>,
+    start: Point,
+    end: Point,
+}
+
+impl Heightmap {
+    fn climbs(&self, at: &Point) -> impl Iterator<Item = Point> + '_ {
+        let from = self.grid[*at];
+        self.grid
+            .neighbors(*at, &Direction::ORTHOGONAL)
+            .filter(move |x| from.reaches(&self.grid[*x]))
+    }
+    /// fewest steps from any of `starts` to the best signal
+    fn shortest(&self, starts: impl IntoIterator<Item = Point>) -> Option<Path<Point>> {
+        bfs(starts, |x| self.climbs(x), |x| *x == self.end)
+    }
+    pub fn from_start(&self) -> Option<Path<Point>> {
+        self.shortest([self.start])
+    }
+    pub fn from_lowest(&self) -> Option<Path<Point>> {
+        self.shortest(
+            self.grid
+                .iter()
+                .filter(|(_, x)| x.0 == 0)
+                .map(|(at, _)| at)
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeightmapError {
+    Empty,
+    InvalidElevation(char),
+    RaggedRow { expected: usize, found: usize },
+    MissingMarker(char),
+    DuplicateMarker(char),
+}
+
+impl Display for HeightmapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "expected at least one row of squares"),
+            Self::InvalidElevation(c) => write!(f, "expected an elevation, found `{c}`"),
+            Self::RaggedRow { expected, found } => {
+                write!(f, "expected {expected} squares in the row, found {found}")
+            }
+            Self::MissingMarker(c) => write!(f, "expected one square marked `{c}`, found none"),
+            Self::DuplicateMarker(c) => {
+                write!(f, "expected one square marked `{c}`, found another")
+            }
+        }
+    }
+}
+
+impl Grammar for HeightmapError {
+    fn expected(&self) -> &'static str {
+        "<a-z|S|E>..., with rows of the same width, one `S` and one `E`"
+    }
+    fn hint(&self) -> Option<String> {
+        match self {
+            Self::MissingMarker(_) | Self::DuplicateMarker(_) => {
+                Some("`S` marks the current position and `E` the best signal".into())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<GridError<char>> for HeightmapError {
+    fn from(v: GridError<char>) -> Self {
+        match v {
+            GridError::Empty => Self::Empty,
+            GridError::RaggedRow { expected, found } => Self::RaggedRow { expected, found },
+            GridError::InvalidCell(c) => Self::InvalidElevation(c),
+        }
+    }
+}
+
+impl FromStr for Heightmap {
+    type Err = ParseError<HeightmapError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse(v, Elevation::try_from).map_err(|e| e.map(HeightmapError::from))?;
+        let marker = |c: char| {
+            let mut found = v.lines().enumerate().flat_map(|(y, line)| {
+                line.char_indices()
+                    .filter(move |(_, x)| *x == c)
+                    .map(move |(x, _)| Point::new(x as isize, y as isize))
+            });
+            let Some(at) = found.next() else {
+                return Err(ParseError::new(
+                    HeightmapError::MissingMarker(c),
+                    Span::new(1, 1, 1),
+                ));
+            };
+            match found.next() {
+                Some(other) => Err(ParseError::new(
+                    HeightmapError::DuplicateMarker(c),
+                    Span::new(other.y as usize + 1, other.x as usize + 1, 1),
+                )),
+                None => Ok(at),
+            }
+        };
+        Ok(Self {
+            start: marker('S')?,
+            end: marker('E')?,
+            grid,
+        })
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Hill Climbing Algorithm";
+
+    type Parsed = Heightmap;
+    type Error = ParseError<HeightmapError>;
+    type One = Result<usize, Unsolvable>;
+    type Two = Result<usize, Unsolvable>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(heightmap: Self::Parsed) -> Self::One {
+        heightmap
+            .from_start()
+            .map(|x| x.steps())
+            .ok_or_else(|| Unsolvable::new("no path from the start to the best signal"))
+    }
+    fn part_two(heightmap: Self::Parsed) -> Self::Two {
+        heightmap
+            .from_lowest()
+            .map(|x| x.steps())
+            .ok_or_else(|| Unsolvable::new("no path from any square at `a` to the best signal"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{geometry::Point, parse::Span, solution::Solution};
+
+    use super::{Heightmap, HeightmapError, Puzzle};
+
+    const INPUT: &str = "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+    #[test]
+    fn parse() {
+        let heightmap = INPUT.parse::<Heightmap>().unwrap();
+        assert_eq!(heightmap.grid.width(), 8);
+        assert_eq!(heightmap.grid.height(), 5);
+        assert_eq!(heightmap.start, Point::new(0, 0));
+        assert_eq!(heightmap.end, Point::new(5, 2));
+    }
+
+    #[test]
+    fn climb() {
+        let heightmap = INPUT.parse::<Heightmap>().unwrap();
+        let path = heightmap.from_start().unwrap();
+        assert_eq!(path.steps(), 31);
+        assert!(path
+            .nodes
+            .windows(2)
+            .all(|x| heightmap.grid[x[0]].reaches(&heightmap.grid[x[1]])));
+        assert_eq!(heightmap.from_lowest().unwrap().steps(), 29);
+    }
+
+    #[test]
+    fn solution() {
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), Ok(31));
+        assert_eq!(Puzzle::part_two(Puzzle::parse(INPUT).unwrap()), Ok(29));
+        let cliff = Puzzle::parse("SaE").unwrap();
+        assert!(Puzzle::part_one(cliff).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT
+            .replace("yxx", "y2x")
+            .parse::<Heightmap>()
+            .unwrap_err();
+        assert_eq!(err.kind, HeightmapError::InvalidElevation('2'));
+        assert_eq!(err.span, Span::new(2, 6, 1));
+        let err = INPUT.replace('E', "z").parse::<Heightmap>().unwrap_err();
+        assert_eq!(err.kind, HeightmapError::MissingMarker('E'));
+        let err = INPUT
+            .replace("tuv", "tSv")
+            .parse::<Heightmap>()
+            .unwrap_err();
+        assert_eq!(err.kind, HeightmapError::DuplicateMarker('S'));
+        assert_eq!(err.span, Span::new(4, 5, 1));
+    }
+}
@@ -0,0 +1,380 @@
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+use crate::{
+    diagnostics::Grammar,
+    parse::{ParseError, Span},
+    solution::Solution,
+};
+
+/// packet data, either an integer or a list of packet data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Packet {
+    Integer(u32),
+    List(Vec<Packet>),
+}
+
+impl Packet {
+    /// `[[n]]`, as added to the packets when sorting them
+    pub fn divider(n: u32) -> Self {
+        Self::List(vec![Self::List(vec![Self::Integer(n)])])
+    }
+}
+
+/// order of the distress signal, where different packets such as `[[2]]` and `[2]` can be in order
+/// with each other
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Integer(a), Self::Integer(b)) => a.cmp(b),
+            (Self::List(a), Self::List(b)) => a.cmp(b),
+            (Self::Integer(a), Self::List(b)) => [Self::Integer(*a)].as_slice().cmp(b.as_slice()),
+            (Self::List(a), Self::Integer(b)) => a.as_slice().cmp([Self::Integer(*b)].as_slice()),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Packet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(v) => write!(f, "{v}"),
+            Self::List(v) => {
+                write!(f, "[")?;
+                for (idx, packet) in v.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{packet}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Signal(pub Vec<(Packet, Packet)>);
+
+impl Signal {
+    /// sum of the 1-based indices of the pairs already in the right order
+    pub fn ordered(&self) -> usize {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|(_, (left, right))| left < right)
+            .map(|(idx, _)| idx + 1)
+            .sum()
+    }
+    /// every packet along with the dividers, in the right order
+    pub fn sorted(&self) -> Vec<Packet> {
+        let mut packets: Vec<Packet> = self
+            .0
+            .iter()
+            .flat_map(|(left, right)| [left.clone(), right.clone()])
+            .chain([Packet::divider(2), Packet::divider(6)])
+            .collect();
+        packets.sort();
+        packets
+    }
+    /// product of the 1-based indices of the dividers once sorted, where each divider comes after
+    /// the packets in order with it, as in `sorted`, rather than being looked up among them
+    pub fn decoder_key(&self) -> usize {
+        [Packet::divider(2), Packet::divider(6)]
+            .iter()
+            .enumerate()
+            .map(|(idx, divider)| {
+                let before = self
+                    .0
+                    .iter()
+                    .flat_map(|(left, right)| [left, right])
+                    .filter(|x| *x <= divider)
+                    .count();
+                before + idx + 1
+            })
+            .product()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PacketError {
+    Expected(&'static str),
+    InvalidInteger(String),
+    TrailingInput(String),
+    UnpairedPacket(usize),
+}
+
+impl Display for PacketError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Expected(v) => write!(f, "expected {v}"),
+            Self::InvalidInteger(v) => write!(f, "expected an integer, found `{v}`"),
+            Self::TrailingInput(v) => write!(f, "unexpected `{v}` after the packet"),
+            Self::UnpairedPacket(n) => write!(f, "expected a pair of packets, found {n}"),
+        }
+    }
+}
+
+impl Grammar for PacketError {
+    fn expected(&self) -> &'static str {
+        "packet  = [<n|packet>,...]
+pairs   = two packets, one per line, with a blank line between pairs"
+    }
+}
+
+/// recursive descent over a single line, where `at` is the byte offset of the next character
+struct Reader<'a> {
+    line: &'a str,
+    at: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn peek(&self) -> Option<char> {
+        self.line[self.at..].chars().next()
+    }
+    fn error(&self, kind: PacketError) -> ParseError<PacketError> {
+        match self.peek() {
+            Some(c) => ParseError::new(
+                kind,
+                Span::of(self.line, &self.line[self.at..][..c.len_utf8()]),
+            ),
+            None => ParseError::new(kind, Span::after(self.line)),
+        }
+    }
+    fn expect(&mut self, c: char, expected: &'static str) -> Result<(), ParseError<PacketError>> {
+        match self.peek() {
+            Some(x) if x == c => {
+                self.at += 1;
+                Ok(())
+            }
+            _ => Err(self.error(PacketError::Expected(expected))),
+        }
+    }
+    fn list(&mut self) -> Result<Packet, ParseError<PacketError>> {
+        self.expect('[', "`[`")?;
+        let mut packets = vec![];
+        if self.peek() == Some(']') {
+            self.at += 1;
+            return Ok(Packet::List(packets));
+        }
+        loop {
+            packets.push(self.value()?);
+            match self.peek() {
+                Some(',') => self.at += 1,
+                Some(']') => {
+                    self.at += 1;
+                    return Ok(Packet::List(packets));
+                }
+                _ => return Err(self.error(PacketError::Expected("`,` or `]`"))),
+            }
+        }
+    }
+    fn value(&mut self) -> Result<Packet, ParseError<PacketError>> {
+        match self.peek() {
+            Some('[') => self.list(),
+            Some(c) if c.is_ascii_digit() => {
+                let digits = &self.line[self.at..];
+                let len = digits
+                    .find(|x: char| !x.is_ascii_digit())
+                    .unwrap_or(digits.len());
+                let token = &digits[..len];
+                let v = token.parse().map_err(|_| {
+                    ParseError::new(
+                        PacketError::InvalidInteger(token.to_string()),
+                        Span::of(self.line, token),
+                    )
+                })?;
+                self.at += len;
+                Ok(Packet::Integer(v))
+            }
+            _ => Err(self.error(PacketError::Expected("an integer or `[`"))),
+        }
+    }
+}
+
+impl FromStr for Packet {
+    type Err = ParseError<PacketError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let mut reader = Reader { line: v, at: 0 };
+        let packet = reader.list()?;
+        match v[reader.at..].trim_end() {
+            "" => Ok(packet),
+            extra => Err(ParseError::new(
+                PacketError::TrailingInput(extra.to_string()),
+                Span::of(v, extra),
+            )),
+        }
+    }
+}
+
+impl FromStr for Signal {
+    type Err = ParseError<PacketError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let mut pairs = vec![];
+        let mut pair: Vec<(usize, Packet)> = vec![];
+        let lines = v.lines().map(Some).chain([None]).enumerate();
+        for (idx, line) in lines {
+            match line.map(str::trim_end) {
+                Some("") | None => {
+                    match pair.as_slice() {
+                        [] => {}
+                        [(_, left), (_, right)] => pairs.push((left.clone(), right.clone())),
+                        [(first, _), ..] => {
+                            return Err(ParseError::new(
+                                PacketError::UnpairedPacket(pair.len()),
+                                Span::new(first + 1, 1, 1),
+                            ))
+                        }
+                    }
+                    pair.clear();
+                }
+                Some(line) => pair.push((idx, line.parse().map_err(|e: Self::Err| e.shift(idx))?)),
+            }
+        }
+        Ok(Self(pairs))
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Distress Signal";
+
+    type Parsed = Signal;
+    type Error = ParseError<PacketError>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(signal: Self::Parsed) -> Self::One {
+        signal.ordered()
+    }
+    fn part_two(signal: Self::Parsed) -> Self::Two {
+        signal.decoder_key()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse::Span, solution::Solution};
+
+    use super::{Packet, PacketError, Puzzle, Signal};
+
+    const INPUT: &str = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]";
+
+    #[test]
+    fn parse() {
+        let signal = INPUT.parse::<Signal>().unwrap();
+        assert_eq!(signal.0.len(), 8);
+        assert_eq!(
+            signal.0[1].1,
+            Packet::List(vec![
+                Packet::List(vec![Packet::Integer(1)]),
+                Packet::Integer(4)
+            ])
+        );
+        for line in INPUT.lines().filter(|x| !x.is_empty()) {
+            assert_eq!(line.parse::<Packet>().unwrap().to_string(), line);
+        }
+        assert_eq!("[10,[]]".parse::<Packet>().unwrap().to_string(), "[10,[]]");
+    }
+
+    #[test]
+    fn order() {
+        let signal = INPUT.parse::<Signal>().unwrap();
+        let ordered: Vec<bool> = signal.0.iter().map(|(l, r)| l < r).collect();
+        assert_eq!(
+            ordered,
+            vec![true, true, false, true, false, true, false, false]
+        );
+        assert!("[[1],[2,3,4]]".parse::<Packet>().unwrap() < "[[1],4]".parse().unwrap());
+        assert_eq!(
+            "[[2]]"
+                .parse::<Packet>()
+                .unwrap()
+                .cmp(&"[2]".parse().unwrap()),
+            std::cmp::Ordering::Equal
+        );
+        // in order with each other, yet different packets
+        assert_ne!("[[2]]".parse::<Packet>().unwrap(), "[2]".parse().unwrap());
+        assert_ne!(Packet::Integer(3), Packet::List(vec![Packet::Integer(3)]));
+    }
+
+    #[test]
+    fn sort() {
+        let signal = INPUT.parse::<Signal>().unwrap();
+        let sorted = signal.sorted();
+        assert_eq!(sorted.first().unwrap().to_string(), "[]");
+        assert_eq!(sorted.get(9), Some(&Packet::divider(2)));
+        assert_eq!(sorted.get(13), Some(&Packet::divider(6)));
+        assert_eq!(sorted.last().unwrap().to_string(), "[9]");
+        // packets in order with the dividers, sorted before them
+        let signal = format!("{INPUT}\n\n[2]\n[[[6]]]")
+            .parse::<Signal>()
+            .unwrap();
+        let sorted = signal.sorted();
+        assert_eq!(sorted[9].to_string(), "[2]");
+        assert_eq!(sorted[10], Packet::divider(2));
+        assert_eq!(sorted[14].to_string(), "[[[6]]]");
+        assert_eq!(sorted[15], Packet::divider(6));
+        assert_eq!(signal.decoder_key(), 11 * 16);
+    }
+
+    #[test]
+    fn solution() {
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), 13);
+        assert_eq!(Puzzle::part_two(Puzzle::parse(INPUT).unwrap()), 140);
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT
+            .replace("[[8,7,6]]", "[[8,7;6]]")
+            .parse::<Signal>()
+            .unwrap_err();
+        assert_eq!(err.kind, PacketError::Expected("`,` or `]`"));
+        assert_eq!(err.span, Span::new(8, 6, 1));
+        let err = "[1,[2]".parse::<Packet>().unwrap_err();
+        assert_eq!(err.kind, PacketError::Expected("`,` or `]`"));
+        assert_eq!(err.span, Span::new(1, 7, 1));
+        let err = "[1,,2]".parse::<Packet>().unwrap_err();
+        assert_eq!(err.kind, PacketError::Expected("an integer or `[`"));
+        assert_eq!(err.span, Span::new(1, 4, 1));
+        let err = "[1]]".parse::<Packet>().unwrap_err();
+        assert_eq!(err.kind, PacketError::TrailingInput("]".into()));
+        let err = "[99999999999]".parse::<Packet>().unwrap_err();
+        assert_eq!(err.kind, PacketError::InvalidInteger("99999999999".into()));
+        let err = INPUT.replace("[9]\n", "").parse::<Signal>().unwrap_err();
+        assert_eq!(err.kind, PacketError::UnpairedPacket(1));
+        assert_eq!(err.span, Span::new(7, 1, 1));
+    }
+}
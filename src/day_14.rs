@@ -0,0 +1,324 @@
+use std::{collections::HashSet, fmt::Display, str::FromStr};
+
+use crate::{
+    diagnostics::Grammar,
+    geometry::{Direction, Point},
+    grid::Grid,
+    parse::{self, ParseError, Span},
+    solution::Solution,
+};
+
+/// where sand pours into the cave from
+pub const SOURCE: Point = Point::new(500, 0);
+
+/// line of rock, through each of its points
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RockPath(pub Vec<Point>);
+
+impl RockPath {
+    /// every point covered by rock along the path
+    fn rocks(&self) -> impl Iterator<Item = Point> + '_ {
+        self.0
+            .windows(2)
+            .flat_map(|x| {
+                let direction = Direction::try_from((x[1] - x[0]).signum()).ok();
+                std::iter::successors(Some(x[0]), move |at| {
+                    (*at != x[1]).then(|| at.step(direction.expect("straight segment")))
+                })
+            })
+            .chain(self.0.first().copied())
+    }
+}
+
+#[derive(Debug)]
+pub struct Scan(pub Vec<RockPath>);
+
+/// what happened to a unit of sand once poured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pour {
+    Rest(Point),
+    Abyss,
+    /// the source itself is covered with sand
+    Blocked,
+}
+
+/// cave being filled with sand, one unit at a time
+#[derive(Debug, Clone)]
+pub struct Reservoir {
+    rocks: HashSet<Point>,
+    sand: HashSet<Point>,
+    /// lowest rock, below which sand falls forever
+    lowest: isize,
+    /// endless floor, two below the lowest rock
+    floor: bool,
+}
+
+impl Reservoir {
+    pub fn new(scan: &Scan, floor: bool) -> Self {
+        let rocks: HashSet<Point> = scan.0.iter().flat_map(|x| x.rocks()).collect();
+        Self {
+            lowest: rocks.iter().map(|x| x.y).max().unwrap_or(0),
+            rocks,
+            sand: HashSet::new(),
+            floor,
+        }
+    }
+    fn blocked(&self, at: &Point) -> bool {
+        self.rocks.contains(at) || self.sand.contains(at) || (self.floor && at.y == self.lowest + 2)
+    }
+    /// pours a unit of sand and lets it fall until it comes to rest, if ever
+    pub fn pour(&mut self) -> Pour {
+        if self.blocked(&SOURCE) {
+            return Pour::Blocked;
+        }
+        let mut at = SOURCE;
+        loop {
+            if !self.floor && at.y > self.lowest {
+                return Pour::Abyss;
+            }
+            let next = [Direction::Down, Direction::DownLeft, Direction::DownRight]
+                .into_iter()
+                .map(|x| at.step(x))
+                .find(|x| !self.blocked(x));
+            match next {
+                Some(next) => at = next,
+                None => {
+                    self.sand.insert(at);
+                    return Pour::Rest(at);
+                }
+            }
+        }
+    }
+    /// pours until sand stops coming to rest, returning how many units did
+    pub fn fill(&mut self) -> usize {
+        while let Pour::Rest(_) = self.pour() {}
+        self.sand.len()
+    }
+    pub fn at_rest(&self) -> usize {
+        self.sand.len()
+    }
+    /// cave as drawn in the puzzle, around the source, rocks and sand
+    pub fn draw(&self) -> String {
+        let points = || {
+            self.rocks
+                .iter()
+                .chain(self.sand.iter())
+                .chain([SOURCE].iter())
+        };
+        let left = points().map(|x| x.x).min().unwrap_or(SOURCE.x);
+        let right = points().map(|x| x.x).max().unwrap_or(SOURCE.x);
+        let top = points().map(|x| x.y).min().unwrap_or(SOURCE.y);
+        let bottom = match self.floor {
+            true => self.lowest + 2,
+            false => points().map(|x| x.y).max().unwrap_or(SOURCE.y),
+        };
+        let corner = Point::new(left, top);
+        let width = (right - left + 1) as usize;
+        let height = (bottom - corner.y + 1) as usize;
+        let mut cave = Grid::new(width, height, '.');
+        for at in cave.positions().collect::<Vec<_>>() {
+            let point = Point::new(at.x + corner.x, at.y + corner.y);
+            cave[at] = match point {
+                _ if point == SOURCE && !self.sand.contains(&point) => '+',
+                _ if self.sand.contains(&point) => 'o',
+                _ if self.blocked(&point) => '#',
+                _ => '.',
+            };
+        }
+        cave.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanError {
+    InvalidPoint(String),
+    DiagonalSegment,
+}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPoint(v) => write!(f, "expected a point, found `{v}`"),
+            Self::DiagonalSegment => write!(f, "expected a horizontal or vertical segment"),
+        }
+    }
+}
+
+impl Grammar for ScanError {
+    fn expected(&self) -> &'static str {
+        "<x>,<y> -> <x>,<y> -> ..."
+    }
+    fn hint(&self) -> Option<String> {
+        match self {
+            Self::DiagonalSegment => {
+                Some("consecutive points should share either their x or their y".into())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for RockPath {
+    type Err = ParseError<ScanError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let mut points: Vec<Point> = vec![];
+        for token in v.split("->").map(str::trim) {
+            let invalid =
+                || ParseError::new(ScanError::InvalidPoint(token.into()), Span::of(v, token));
+            let (x, y) = token.split_once(',').ok_or_else(invalid)?;
+            let point = Point::new(
+                x.parse().map_err(|_| invalid())?,
+                y.parse().map_err(|_| invalid())?,
+            );
+            if let Some(previous) = points.last() {
+                if previous.x != point.x && previous.y != point.y {
+                    return Err(ParseError::new(
+                        ScanError::DiagonalSegment,
+                        Span::of(v, token),
+                    ));
+                }
+            }
+            points.push(point);
+        }
+        Ok(Self(points))
+    }
+}
+
+impl FromStr for Scan {
+    type Err = ParseError<ScanError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        Ok(Self(parse::lines(v)?))
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Regolith Reservoir";
+
+    type Parsed = Scan;
+    type Error = ParseError<ScanError>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(scan: Self::Parsed) -> Self::One {
+        Reservoir::new(&scan, false).fill()
+    }
+    fn part_two(scan: Self::Parsed) -> Self::Two {
+        Reservoir::new(&scan, true).fill()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{geometry::Point, parse::Span, solution::Solution};
+
+    use super::{Pour, Puzzle, Reservoir, RockPath, Scan, ScanError};
+
+    const INPUT: &str = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+
+    #[test]
+    fn parse() {
+        let scan = INPUT.parse::<Scan>().unwrap();
+        assert_eq!(scan.0.len(), 2);
+        assert_eq!(
+            scan.0[0].0,
+            vec![(498, 4).into(), (498, 6).into(), (496, 6).into()]
+        );
+        let rocks = |x: &RockPath| x.rocks().collect::<HashSet<_>>().len();
+        assert_eq!(rocks(&scan.0[0]), 5);
+        assert_eq!(rocks(&scan.0[1]), 15);
+    }
+
+    #[test]
+    fn pour() {
+        let scan = INPUT.parse::<Scan>().unwrap();
+        let mut reservoir = Reservoir::new(&scan, false);
+        assert_eq!(
+            reservoir.draw(),
+            "......+...
+..........
+..........
+..........
+....#...##
+....#...#.
+..###...#.
+........#.
+........#.
+#########."
+        );
+        assert_eq!(reservoir.pour(), Pour::Rest(Point::new(500, 8)));
+        assert_eq!(reservoir.pour(), Pour::Rest(Point::new(499, 8)));
+        for _ in 2..24 {
+            assert!(matches!(reservoir.pour(), Pour::Rest(_)));
+        }
+        assert_eq!(
+            reservoir.draw(),
+            "......+...
+..........
+......o...
+.....ooo..
+....#ooo##
+...o#ooo#.
+..###ooo#.
+....oooo#.
+.o.ooooo#.
+#########."
+        );
+        assert_eq!(reservoir.pour(), Pour::Abyss);
+        assert_eq!(reservoir.at_rest(), 24);
+    }
+
+    #[test]
+    fn floor() {
+        let scan = INPUT.parse::<Scan>().unwrap();
+        let mut reservoir = Reservoir::new(&scan, true);
+        assert_eq!(reservoir.fill(), 93);
+        assert_eq!(reservoir.pour(), Pour::Blocked);
+        assert_eq!(
+            reservoir.draw(),
+            "..........o..........
+.........ooo.........
+........ooooo........
+.......ooooooo.......
+......oo#ooo##o......
+.....ooo#ooo#ooo.....
+....oo###ooo#oooo....
+...oooo.oooo#ooooo...
+..oooooooooo#oooooo..
+.ooo#########ooooooo.
+ooooo.......ooooooooo
+#####################"
+        );
+    }
+
+    #[test]
+    fn above() {
+        let scan = "499,-2 -> 501,-2\n498,2 -> 502,2".parse::<Scan>().unwrap();
+        let reservoir = Reservoir::new(&scan, false);
+        assert_eq!(reservoir.draw(), ".###.\n.....\n..+..\n.....\n#####");
+    }
+
+    #[test]
+    fn solution() {
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), 24);
+        assert_eq!(Puzzle::part_two(Puzzle::parse(INPUT).unwrap()), 93);
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT.replace("502,9", "502;9").parse::<Scan>().unwrap_err();
+        assert_eq!(err.kind, ScanError::InvalidPoint("502;9".into()));
+        assert_eq!(err.span, Span::new(2, 19, 5));
+        let err = "498,4 -> 497,6".parse::<RockPath>().unwrap_err();
+        assert_eq!(err.kind, ScanError::DiagonalSegment);
+        assert_eq!(err.span, Span::new(1, 10, 5));
+    }
+}
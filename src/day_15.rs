@@ -0,0 +1,235 @@
+use std::{collections::HashSet, fmt::Display, str::FromStr};
+
+use crate::{
+    diagnostics::Grammar,
+    geometry::Point,
+    interval::{Interval, IntervalSet},
+    parse::{self, ParseError, Span},
+    solution::{Solution, Unsolvable},
+};
+
+/// row checked for positions where no beacon can be
+pub const ROW: isize = 2_000_000;
+/// the distress beacon is within `0..=BOUND` on both axes
+pub const BOUND: isize = 4_000_000;
+
+/// sensor along with the closest beacon it detects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sensor {
+    pub at: Point,
+    pub beacon: Point,
+}
+
+impl Sensor {
+    /// distance up to which there can be no other beacon
+    pub fn radius(&self) -> isize {
+        self.at.manhattan(&self.beacon) as isize
+    }
+    /// positions on `row` within the radius of the sensor, if any
+    pub fn coverage(&self, row: isize) -> Option<Interval<isize>> {
+        let reach = self.radius() - (row - self.at.y).abs();
+        Interval::new(self.at.x - reach, self.at.x + reach)
+    }
+}
+
+#[derive(Debug)]
+pub struct Sensors(pub Vec<Sensor>);
+
+impl Sensors {
+    /// positions on `row` within the radius of any sensor
+    pub fn coverage(&self, row: isize) -> IntervalSet<isize> {
+        self.0.iter().filter_map(|x| x.coverage(row)).collect()
+    }
+    /// positions on `row` where there cannot be a beacon
+    pub fn excluded(&self, row: isize) -> usize {
+        let coverage = self.coverage(row);
+        let beacons = self
+            .0
+            .iter()
+            .map(|x| x.beacon)
+            .filter(|x| x.y == row && coverage.contains(x.x))
+            .collect::<HashSet<_>>();
+        coverage.len() as usize - beacons.len()
+    }
+    /// only position within `0..=bound` on both axes that no sensor covers
+    pub fn distress(&self, bound: isize) -> Option<Point> {
+        let bounds = Interval::new(0, bound)?;
+        (0..=bound).find_map(|y| {
+            let gap = self.coverage(y).gaps(bounds).next()?;
+            Some(Point::new(gap.start, y))
+        })
+    }
+}
+
+/// frequency identifying the distress beacon
+pub fn tuning_frequency(at: Point) -> isize {
+    at.x * 4_000_000 + at.y
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SensorError {
+    MissingSensor,
+    MissingBeacon,
+    InvalidPosition(String),
+}
+
+impl Display for SensorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingSensor => write!(f, "expected `Sensor at `"),
+            Self::MissingBeacon => write!(f, "expected `: closest beacon is at `"),
+            Self::InvalidPosition(v) => write!(f, "expected a position, found `{v}`"),
+        }
+    }
+}
+
+impl Grammar for SensorError {
+    fn expected(&self) -> &'static str {
+        "Sensor at x=<n>, y=<n>: closest beacon is at x=<n>, y=<n>"
+    }
+}
+
+/// position written `x=<n>, y=<n>`, where `token` is a slice of `line`
+fn position(line: &str, token: &str) -> Result<Point, ParseError<SensorError>> {
+    let invalid = || {
+        ParseError::new(
+            SensorError::InvalidPosition(token.to_string()),
+            Span::of(line, token),
+        )
+    };
+    let (x, y) = token.split_once(", ").ok_or_else(invalid)?;
+    let coordinate = |v: &str, prefix: &str| {
+        v.strip_prefix(prefix)
+            .and_then(|x| x.parse::<isize>().ok())
+            .ok_or_else(invalid)
+    };
+    Ok(Point::new(coordinate(x, "x=")?, coordinate(y, "y=")?))
+}
+
+impl FromStr for Sensor {
+    type Err = ParseError<SensorError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let line = v.trim_end();
+        let rest = line
+            .strip_prefix("Sensor at ")
+            .ok_or_else(|| ParseError::new(SensorError::MissingSensor, Span::new(1, 1, 1)))?;
+        let (at, beacon) = rest
+            .split_once(": closest beacon is at ")
+            .ok_or_else(|| ParseError::new(SensorError::MissingBeacon, Span::after(line)))?;
+        Ok(Self {
+            at: position(line, at)?,
+            beacon: position(line, beacon)?,
+        })
+    }
+}
+
+impl FromStr for Sensors {
+    type Err = ParseError<SensorError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        Ok(Self(parse::lines(v)?))
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "Beacon Exclusion Zone";
+
+    type Parsed = Sensors;
+    type Error = ParseError<SensorError>;
+    type One = usize;
+    type Two = Result<isize, Unsolvable>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(sensors: Self::Parsed) -> Self::One {
+        sensors.excluded(ROW)
+    }
+    fn part_two(sensors: Self::Parsed) -> Self::Two {
+        sensors
+            .distress(BOUND)
+            .map(tuning_frequency)
+            .ok_or_else(|| Unsolvable::new(format!("every position up to {BOUND} is covered")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{geometry::Point, interval::Interval, parse::Span};
+
+    use super::{tuning_frequency, Sensor, SensorError, Sensors};
+
+    const INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+    #[test]
+    fn parse() {
+        let sensors = INPUT.parse::<Sensors>().unwrap();
+        assert_eq!(sensors.0.len(), 14);
+        assert_eq!(
+            sensors.0[0],
+            Sensor {
+                at: Point::new(2, 18),
+                beacon: Point::new(-2, 15)
+            }
+        );
+    }
+
+    #[test]
+    fn coverage() {
+        let sensors = INPUT.parse::<Sensors>().unwrap();
+        let sensor = sensors.0[6];
+        assert_eq!(sensor.radius(), 9);
+        assert_eq!(sensor.coverage(7), Interval::new(-1, 17));
+        assert_eq!(sensor.coverage(16), Interval::new(8, 8));
+        assert_eq!(sensor.coverage(17), None);
+        let row = sensors.coverage(10);
+        assert_eq!(row.iter().collect::<Vec<_>>(), vec![&(-2..=24).into()]);
+        assert_eq!(sensors.excluded(10), 26);
+    }
+
+    #[test]
+    fn solution() {
+        let sensors = INPUT.parse::<Sensors>().unwrap();
+        let distress = sensors.distress(20).unwrap();
+        assert_eq!(distress, Point::new(14, 11));
+        assert_eq!(tuning_frequency(distress), 56000011);
+        let covered = "Sensor at x=0, y=0: closest beacon is at x=0, y=50"
+            .parse::<Sensors>()
+            .unwrap();
+        assert_eq!(covered.distress(20), None);
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT
+            .replace("x=9, y=16", "x=9; y=16")
+            .parse::<Sensors>()
+            .unwrap_err();
+        assert_eq!(err.kind, SensorError::InvalidPosition("x=9; y=16".into()));
+        assert_eq!(err.span, Span::new(2, 11, 9));
+        let err = "Sensor x=2, y=18".parse::<Sensor>().unwrap_err();
+        assert_eq!(err.kind, SensorError::MissingSensor);
+        let err = "Sensor at x=2, y=18".parse::<Sensor>().unwrap_err();
+        assert_eq!(err.kind, SensorError::MissingBeacon);
+        assert_eq!(err.span, Span::new(1, 20, 1));
+        let err = "Sensor at x=2, y=18: closest beacon is at x=-2, z=15"
+            .parse::<Sensor>()
+            .unwrap_err();
+        assert_eq!(err.kind, SensorError::InvalidPosition("x=-2, z=15".into()));
+    }
+}
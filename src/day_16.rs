@@ -0,0 +1,440 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    fmt::Display,
+    str::FromStr,
+};
+
+use crate::{
+    diagnostics::Grammar,
+    parse::{ParseError, Span},
+    pathfinding::distances,
+    solution::Solution,
+};
+
+/// valve everyone starts from
+pub const START: &str = "AA";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Valve {
+    pub name: String,
+    pub rate: u32,
+    pub tunnels: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct Scan(pub Vec<Valve>);
+
+/// valves worth opening along with the start, and the fewest minutes to walk between any two
+#[derive(Debug, Clone)]
+pub struct Graph {
+    /// the start first, then the valves with a positive flow rate
+    pub names: Vec<String>,
+    pub rates: Vec<u32>,
+    /// `u32::MAX` between valves out of reach of each other
+    pub distances: Vec<Vec<u32>>,
+}
+
+impl Graph {
+    /// drops the valves with no flow rate, keeping only the walks between the others
+    pub fn compress(scan: &Scan) -> Self {
+        let tunnels: HashMap<&str, &[String]> = scan
+            .0
+            .iter()
+            .map(|x| (x.name.as_str(), x.tunnels.as_slice()))
+            .collect();
+        let kept: Vec<&Valve> = scan
+            .0
+            .iter()
+            .filter(|x| x.name == START)
+            .chain(scan.0.iter().filter(|x| x.name != START && x.rate > 0))
+            .collect();
+        let distances = kept
+            .iter()
+            .map(|from| {
+                let found = distances(from.name.as_str(), |x| {
+                    let next: &[String] = tunnels.get(x).copied().unwrap_or_default();
+                    next.iter().map(String::as_str)
+                });
+                kept.iter()
+                    .map(|to| found.get(to.name.as_str()).map_or(u32::MAX, |x| *x as u32))
+                    .collect()
+            })
+            .collect();
+        Self {
+            names: kept.iter().map(|x| x.name.clone()).collect(),
+            rates: kept.iter().map(|x| x.rate).collect(),
+            distances,
+        }
+    }
+    /// fewest minutes to walk from one valve to another, by name
+    pub fn distance(&self, from: &str, to: &str) -> Option<u32> {
+        let idx = |name| self.names.iter().position(|x| x == name);
+        Some(self.distances[idx(from)?][idx(to)?])
+    }
+}
+
+/// every walk through a compressed graph, remembering the most pressure released along the way
+#[derive(Debug)]
+pub struct Search<'a> {
+    graph: &'a Graph,
+    /// most pressure on reaching a valve with minutes left and opened valves, to skip worse walks
+    seen: HashMap<(usize, u32, u64), u32>,
+    /// most pressure released by the end, for each set of opened valves
+    released: HashMap<u64, u32>,
+}
+
+impl<'a> Search<'a> {
+    /// walks from the start for `minutes`
+    pub fn new(graph: &'a Graph, minutes: u32) -> Self {
+        let mut search = Self {
+            graph,
+            seen: HashMap::new(),
+            released: HashMap::new(),
+        };
+        search.walk(0, minutes, 0, 0);
+        search
+    }
+    fn walk(&mut self, at: usize, minutes: u32, opened: u64, pressure: u32) {
+        match self.seen.entry((at, minutes, opened)) {
+            Entry::Occupied(entry) if *entry.get() >= pressure => return,
+            entry => *entry.or_default() = pressure,
+        }
+        let best = self.released.entry(opened).or_default();
+        *best = pressure.max(*best);
+        for next in 0..self.graph.rates.len() {
+            let bit = 1 << next;
+            if self.graph.rates[next] == 0 || opened & bit != 0 {
+                continue;
+            }
+            let walk = self.graph.distances[at][next].saturating_add(1);
+            let Some(left) = minutes.checked_sub(walk).filter(|x| *x > 0) else {
+                continue;
+            };
+            self.walk(
+                next,
+                left,
+                opened | bit,
+                pressure + self.graph.rates[next] * left,
+            );
+        }
+    }
+    /// most pressure released by the end, for each set of opened valves as a bitmask of graph indices
+    pub fn released(&self) -> &HashMap<u64, u32> {
+        &self.released
+    }
+    /// most pressure released when walking alone
+    pub fn alone(&self) -> u32 {
+        self.released.values().copied().max().unwrap_or_default()
+    }
+    /// most pressure released by two walking at the same time, never opening the same valve
+    pub fn together(&self) -> u32 {
+        let mut released: Vec<(u64, u32)> = self.released.iter().map(|(k, v)| (*k, *v)).collect();
+        released.sort_by_key(|(_, x)| std::cmp::Reverse(*x));
+        let mut best = 0;
+        for (idx, (mine, first)) in released.iter().enumerate() {
+            if first * 2 <= best {
+                break;
+            }
+            for (theirs, second) in released[idx..].iter() {
+                if first + second <= best {
+                    break;
+                }
+                if mine & theirs == 0 {
+                    best = first + second;
+                }
+            }
+        }
+        best
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValveError {
+    Expected(&'static str),
+    InvalidName(String),
+    InvalidRate(String),
+    UnknownValve(String),
+    MissingStart,
+    TooManyValves(usize),
+}
+
+impl Display for ValveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Expected(v) => write!(f, "expected {v}"),
+            Self::InvalidName(v) => write!(f, "expected a valve name, found `{v}`"),
+            Self::InvalidRate(v) => write!(f, "expected a flow rate, found `{v}`"),
+            Self::UnknownValve(v) => write!(f, "no valve named `{v}`"),
+            Self::MissingStart => write!(f, "expected a valve named `{START}`"),
+            Self::TooManyValves(n) => {
+                write!(
+                    f,
+                    "expected at most 64 valves with a flow rate, counting `{START}`, found {n}"
+                )
+            }
+        }
+    }
+}
+
+impl Grammar for ValveError {
+    fn expected(&self) -> &'static str {
+        "Valve <name> has flow rate=<n>; tunnels lead to valves <name>, ..."
+    }
+    fn hint(&self) -> Option<String> {
+        match self {
+            Self::UnknownValve(_) => {
+                Some("every tunnel should lead to a valve described on its own line".into())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// valve name, where `token` is a slice of `line`
+fn name(line: &str, token: &str) -> Result<String, ParseError<ValveError>> {
+    match !token.is_empty() && token.chars().all(|x| x.is_ascii_uppercase()) {
+        true => Ok(token.to_string()),
+        false => Err(ParseError::new(
+            ValveError::InvalidName(token.to_string()),
+            Span::of(line, token),
+        )),
+    }
+}
+
+impl FromStr for Valve {
+    type Err = ParseError<ValveError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let line = v.trim_end();
+        let expected = |what| ParseError::new(ValveError::Expected(what), Span::after(line));
+        let rest = line
+            .strip_prefix("Valve ")
+            .ok_or_else(|| ParseError::new(ValveError::Expected("`Valve `"), Span::new(1, 1, 1)))?;
+        let (valve, rest) = rest
+            .split_once(" has flow rate=")
+            .ok_or_else(|| expected("` has flow rate=`"))?;
+        let (rate, rest) = rest.split_once("; ").ok_or_else(|| expected("`; `"))?;
+        let tunnels = rest
+            .strip_prefix("tunnels lead to valves ")
+            .or_else(|| rest.strip_prefix("tunnel leads to valve "))
+            .ok_or_else(|| {
+                ParseError::new(
+                    ValveError::Expected("`tunnels lead to valves `"),
+                    Span::of(line, rest),
+                )
+            })?;
+        Ok(Self {
+            name: name(line, valve)?,
+            rate: rate.parse().map_err(|_| {
+                ParseError::new(
+                    ValveError::InvalidRate(rate.to_string()),
+                    Span::of(line, rate),
+                )
+            })?,
+            tunnels: tunnels
+                .split(", ")
+                .map(|x| name(line, x))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl FromStr for Scan {
+    type Err = ParseError<ValveError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let mut valves: Vec<(usize, &str, Valve)> = vec![];
+        for (idx, line) in v.lines().enumerate() {
+            if !line.trim().is_empty() {
+                let valve = line.parse::<Valve>().map_err(|e| e.shift(idx))?;
+                valves.push((idx, line, valve));
+            }
+        }
+        let names: HashSet<&str> = valves.iter().map(|(_, _, x)| x.name.as_str()).collect();
+        for (idx, line, valve) in valves.iter() {
+            if let Some(unknown) = valve.tunnels.iter().find(|x| !names.contains(x.as_str())) {
+                let tunnels = &line[line.find(';').unwrap_or_default()..];
+                let token = tunnels
+                    .split([' ', ','])
+                    .find(|x| x == unknown)
+                    .unwrap_or(line);
+                return Err(ParseError::new(
+                    ValveError::UnknownValve(unknown.clone()),
+                    Span::of(line, token).shift(*idx),
+                ));
+            }
+        }
+        if !names.contains(START) {
+            return Err(ParseError::new(
+                ValveError::MissingStart,
+                Span::new(1, 1, 1),
+            ));
+        }
+        // the start and every valve with a flow rate each take a bit once compressed
+        let kept = valves
+            .iter()
+            .filter(|(_, _, x)| x.name == START || x.rate > 0)
+            .count();
+        if kept > 64 {
+            return Err(ParseError::new(
+                ValveError::TooManyValves(kept),
+                Span::new(1, 1, 1),
+            ));
+        }
+        Ok(Self(valves.into_iter().map(|(_, _, x)| x).collect()))
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "Proboscidea Volcanium";
+
+    type Parsed = Graph;
+    type Error = ParseError<ValveError>;
+    type One = u32;
+    type Two = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        Ok(Graph::compress(&input.parse()?))
+    }
+    fn part_one(graph: Self::Parsed) -> Self::One {
+        Search::new(&graph, 30).alone()
+    }
+    fn part_two(graph: Self::Parsed) -> Self::Two {
+        Search::new(&graph, 26).together()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse::Span, solution::Solution};
+
+    use super::{Graph, Puzzle, Scan, Search, Valve, ValveError};
+
+    const INPUT: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+
+    #[test]
+    fn parse() {
+        let scan = INPUT.parse::<Scan>().unwrap();
+        assert_eq!(scan.0.len(), 10);
+        assert_eq!(
+            scan.0[7],
+            Valve {
+                name: "HH".into(),
+                rate: 22,
+                tunnels: vec!["GG".into()]
+            }
+        );
+    }
+
+    #[test]
+    fn compress() {
+        let graph = Graph::compress(&INPUT.parse().unwrap());
+        assert_eq!(graph.names, vec!["AA", "BB", "CC", "DD", "EE", "HH", "JJ"]);
+        assert_eq!(graph.rates, vec![0, 13, 2, 20, 3, 22, 21]);
+        assert_eq!(graph.distance("AA", "HH"), Some(5));
+        assert_eq!(graph.distance("JJ", "HH"), Some(7));
+        assert_eq!(graph.distance("CC", "BB"), Some(1));
+        assert_eq!(graph.distance("AA", "FF"), None);
+        assert!((0..graph.names.len()).all(|x| graph.distances[x][x] == 0));
+    }
+
+    #[test]
+    fn search() {
+        let graph = Graph::compress(&INPUT.parse().unwrap());
+        let search = Search::new(&graph, 30);
+        assert_eq!(search.alone(), 1651);
+        let opened = |names: &[&str]| {
+            names
+                .iter()
+                .map(|x| 1 << graph.names.iter().position(|n| n == x).unwrap())
+                .sum::<u64>()
+        };
+        let best = search.released()[&opened(&["BB", "CC", "DD", "EE", "HH", "JJ"])];
+        assert_eq!(best, 1651);
+        assert_eq!(search.released()[&0], 0);
+        assert_eq!(search.released()[&opened(&["DD"])], 20 * 28);
+        let search = Search::new(&graph, 26);
+        assert_eq!(search.together(), 1707);
+        assert_eq!(
+            search.released()[&opened(&["JJ", "BB", "CC"])]
+                + search.released()[&opened(&["DD", "HH", "EE"])],
+            1707
+        );
+        assert_eq!(Search::new(&graph, 2).alone(), 0);
+        assert_eq!(Search::new(&graph, 3).alone(), 20);
+    }
+
+    #[test]
+    fn solution() {
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), 1651);
+        assert_eq!(Puzzle::part_two(Puzzle::parse(INPUT).unwrap()), 1707);
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT
+            .replace("rate=13", "rate=x")
+            .parse::<Scan>()
+            .unwrap_err();
+        assert_eq!(err.kind, ValveError::InvalidRate("x".into()));
+        assert_eq!(err.span, Span::new(2, 24, 1));
+        let err = INPUT
+            .replace("valve GG", "valve gg")
+            .parse::<Scan>()
+            .unwrap_err();
+        assert_eq!(err.kind, ValveError::InvalidName("gg".into()));
+        assert_eq!(err.span, Span::new(8, 50, 2));
+        let err = INPUT
+            .replace("CC, AA, EE", "CC, ZZ, EE")
+            .parse::<Scan>()
+            .unwrap_err();
+        assert_eq!(err.kind, ValveError::UnknownValve("ZZ".into()));
+        assert_eq!(err.span, Span::new(4, 55, 2));
+        let err = INPUT.replace("AA", "KK").parse::<Scan>().unwrap_err();
+        assert_eq!(err.kind, ValveError::MissingStart);
+        // valves in a line from the start, the first `flowing` of them with a flow rate
+        let line = |start: u32, flowing: usize| {
+            let names: Vec<String> = std::iter::once("AA".to_string())
+                .chain((b'B'..=b'Z').flat_map(|a| {
+                    (b'A'..=b'Z').map(move |b| String::from_utf8(vec![a, b]).unwrap())
+                }))
+                .take(flowing + 2)
+                .collect();
+            names
+                .iter()
+                .enumerate()
+                .map(|(idx, name)| {
+                    let rate = match idx {
+                        0 => start,
+                        _ if idx <= flowing => 1,
+                        _ => 0,
+                    };
+                    let next = &names[(idx + 1) % names.len()];
+                    format!("Valve {name} has flow rate={rate}; tunnel leads to valve {next}")
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let graph = Puzzle::parse(&line(0, 63)).unwrap();
+        assert_eq!(graph.names.len(), 64);
+        assert_eq!(Search::new(&graph, 3).alone(), 1);
+        assert!(Puzzle::parse(&line(1, 63)).is_ok());
+        let err = line(0, 64).parse::<Scan>().unwrap_err();
+        assert_eq!(err.kind, ValveError::TooManyValves(65));
+        let err = line(1, 64).parse::<Scan>().unwrap_err();
+        assert_eq!(err.kind, ValveError::TooManyValves(65));
+        let err = "Valve AA has flow rate=0".parse::<Valve>().unwrap_err();
+        assert_eq!(err.kind, ValveError::Expected("`; `"));
+        assert_eq!(err.span, Span::new(1, 25, 1));
+    }
+}
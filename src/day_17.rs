@@ -0,0 +1,274 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    cycle::simulate,
+    diagnostics::Grammar,
+    parse::{ParseError, Span},
+    solution::Solution,
+};
+
+/// chamber width, in units
+pub const WIDTH: usize = 7;
+
+/// rows of each rock from the bottom up, where bit `WIDTH - 1 - x` is column `x`,
+/// as they appear 2 units away from the left wall
+pub const ROCKS: [&[u8]; 5] = [
+    &[0b0011110],
+    &[0b0001000, 0b0011100, 0b0001000],
+    &[0b0011100, 0b0000100, 0b0000100],
+    &[0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    &[0b0011000, 0b0011000],
+];
+
+const LEFT_WALL: u8 = 1 << (WIDTH - 1);
+const RIGHT_WALL: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+#[derive(Debug)]
+pub struct Jets(pub Vec<Jet>);
+
+/// tall, narrow chamber where rocks fall one at a time, pushed around by jets of hot gas
+#[derive(Debug, Clone)]
+pub struct Chamber {
+    jets: Vec<Jet>,
+    /// settled rocks, from the floor up
+    rows: Vec<u8>,
+    /// index of the next jet
+    jet: usize,
+    dropped: usize,
+}
+
+impl Chamber {
+    pub fn new(jets: Jets) -> Self {
+        Self {
+            jets: jets.0,
+            rows: vec![],
+            jet: 0,
+            dropped: 0,
+        }
+    }
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+    fn collides(&self, rock: &[u8], bottom: usize) -> bool {
+        rock.iter()
+            .enumerate()
+            .any(|(idx, row)| self.rows.get(bottom + idx).is_some_and(|x| x & row != 0))
+    }
+    /// drops the next rock until it comes to rest
+    pub fn drop_rock(&mut self) {
+        let mut rock = ROCKS[self.dropped % ROCKS.len()].to_vec();
+        let mut bottom = self.height() + 3;
+        loop {
+            let jet = self.jets[self.jet];
+            self.jet = (self.jet + 1) % self.jets.len();
+            let pushed: Option<Vec<u8>> = match jet {
+                Jet::Left if rock.iter().all(|x| x & LEFT_WALL == 0) => {
+                    Some(rock.iter().map(|x| x << 1).collect())
+                }
+                Jet::Right if rock.iter().all(|x| x & RIGHT_WALL == 0) => {
+                    Some(rock.iter().map(|x| x >> 1).collect())
+                }
+                _ => None,
+            };
+            if let Some(pushed) = pushed.filter(|x| !self.collides(x, bottom)) {
+                rock = pushed;
+            }
+            if bottom == 0 || self.collides(&rock, bottom - 1) {
+                break;
+            }
+            bottom -= 1;
+        }
+        for (idx, row) in rock.into_iter().enumerate() {
+            match self.rows.get_mut(bottom + idx) {
+                Some(x) => *x |= row,
+                None => self.rows.push(row),
+            }
+        }
+        self.dropped += 1;
+    }
+    /// depth of the highest rock in each column, down from the top of the tower
+    pub fn profile(&self) -> [usize; WIDTH] {
+        std::array::from_fn(|x| {
+            let bit = 1 << (WIDTH - 1 - x);
+            self.rows
+                .iter()
+                .rev()
+                .position(|row| row & bit != 0)
+                .unwrap_or(self.height())
+        })
+    }
+    /// height of the tower once `rocks` more rocks have fallen,
+    /// skipping ahead once the next rock, jet and top of the tower are seen again
+    pub fn height_after(mut self, rocks: usize) -> usize {
+        simulate(
+            &mut self,
+            rocks,
+            Self::drop_rock,
+            |x| (x.dropped % ROCKS.len(), x.jet, x.profile()),
+            Self::height,
+        )
+    }
+    /// tower as drawn in the puzzle, with settled rocks only
+    pub fn draw(&self) -> String {
+        self.rows
+            .iter()
+            .rev()
+            .map(|row| {
+                let cells: String = (0..WIDTH)
+                    .map(|x| match row & (1 << (WIDTH - 1 - x)) {
+                        0 => '.',
+                        _ => '#',
+                    })
+                    .collect();
+                format!("|{cells}|")
+            })
+            .chain([format!("+{}+", "-".repeat(WIDTH))])
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JetError {
+    Empty,
+    InvalidJet(char),
+}
+
+impl Display for JetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "expected at least one jet"),
+            Self::InvalidJet(c) => write!(f, "expected `<` or `>`, found `{c}`"),
+        }
+    }
+}
+
+impl Grammar for JetError {
+    fn expected(&self) -> &'static str {
+        "<|>..., on a single line"
+    }
+}
+
+impl FromStr for Jets {
+    type Err = ParseError<JetError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let jets = v.trim();
+        // spans point into the untrimmed input, on the line the jets start at
+        let skipped = &v[..v.len() - v.trim_start().len()];
+        let line = &v[skipped.rfind('\n').map_or(0, |x| x + 1)..];
+        let jets = jets
+            .char_indices()
+            .map(|(idx, c)| match c {
+                '<' => Ok(Jet::Left),
+                '>' => Ok(Jet::Right),
+                _ => Err(ParseError::new(
+                    JetError::InvalidJet(c),
+                    Span::of(line, &jets[idx..idx + c.len_utf8()])
+                        .shift(skipped.matches('\n').count()),
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        match jets.is_empty() {
+            true => Err(ParseError::new(JetError::Empty, Span::new(1, 1, 1))),
+            false => Ok(Self(jets)),
+        }
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 17;
+    const TITLE: &'static str = "Pyroclastic Flow";
+
+    type Parsed = Jets;
+    type Error = ParseError<JetError>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(jets: Self::Parsed) -> Self::One {
+        Chamber::new(jets).height_after(2022)
+    }
+    fn part_two(jets: Self::Parsed) -> Self::Two {
+        Chamber::new(jets).height_after(1_000_000_000_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse::Span, solution::Solution};
+
+    use super::{Chamber, Jet, JetError, Jets, Puzzle};
+
+    const INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+    #[test]
+    fn parse() {
+        let jets = INPUT.parse::<Jets>().unwrap();
+        assert_eq!(jets.0.len(), 40);
+        assert_eq!(jets.0[..4], [Jet::Right, Jet::Right, Jet::Right, Jet::Left]);
+    }
+
+    #[test]
+    fn fall() {
+        let mut chamber = Chamber::new(INPUT.parse().unwrap());
+        chamber.drop_rock();
+        assert_eq!(chamber.draw(), "|..####.|\n+-------+");
+        chamber.drop_rock();
+        assert_eq!(
+            chamber.draw(),
+            "|...#...|
+|..###..|
+|...#...|
+|..####.|
++-------+"
+        );
+        chamber.drop_rock();
+        assert_eq!(
+            chamber.draw(),
+            "|..#....|
+|..#....|
+|####...|
+|..###..|
+|...#...|
+|..####.|
++-------+"
+        );
+        assert_eq!(chamber.profile(), [2, 2, 0, 2, 3, 5, 6]);
+        for _ in 3..2022 {
+            chamber.drop_rock();
+        }
+        assert_eq!(chamber.height(), 3068);
+    }
+
+    #[test]
+    fn solution() {
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), 3068);
+        assert_eq!(
+            Puzzle::part_two(Puzzle::parse(INPUT).unwrap()),
+            1514285714288
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let err = ">><x<".parse::<Jets>().unwrap_err();
+        assert_eq!(err.kind, JetError::InvalidJet('x'));
+        assert_eq!(err.span, Span::new(1, 4, 1));
+        let err = " >><x<".parse::<Jets>().unwrap_err();
+        assert_eq!(err.span, Span::new(1, 5, 1));
+        let err = "\n  >x<".parse::<Jets>().unwrap_err();
+        assert_eq!(err.span, Span::new(2, 4, 1));
+        let err = "\n".parse::<Jets>().unwrap_err();
+        assert_eq!(err.kind, JetError::Empty);
+    }
+}
@@ -0,0 +1,163 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    diagnostics::Grammar,
+    geometry::Point3,
+    parse::{self, ParseError, Span},
+    solution::Solution,
+    voxel::Voxels,
+};
+
+/// 1x1x1 cube of lava, at the position of its lowest corner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cube(pub Point3);
+
+#[derive(Debug)]
+pub struct Droplet(pub Voxels);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CubeError {
+    InvalidCoordinate(String),
+    MissingCoordinate,
+    TooManyCoordinates(String),
+}
+
+impl Display for CubeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidCoordinate(v) => write!(f, "expected a coordinate, found `{v}`"),
+            Self::MissingCoordinate => write!(f, "missing coordinate"),
+            Self::TooManyCoordinates(v) => {
+                write!(f, "there should be only 3 coordinates, found `{v}`")
+            }
+        }
+    }
+}
+
+impl Grammar for CubeError {
+    fn expected(&self) -> &'static str {
+        "<x>,<y>,<z>"
+    }
+}
+
+impl FromStr for Cube {
+    type Err = ParseError<CubeError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let line = v.trim_end();
+        let mut parts = line.split(',');
+        let mut coordinate = || match parts.next() {
+            Some(part) => part.parse::<isize>().map_err(|_| {
+                ParseError::new(
+                    CubeError::InvalidCoordinate(part.to_string()),
+                    Span::of(line, part),
+                )
+            }),
+            None => Err(ParseError::new(
+                CubeError::MissingCoordinate,
+                Span::after(line),
+            )),
+        };
+        let at = Point3::new(coordinate()?, coordinate()?, coordinate()?);
+        if let Some(part) = parts.next() {
+            return Err(ParseError::new(
+                CubeError::TooManyCoordinates(part.to_string()),
+                Span::of(line, part),
+            ));
+        }
+        Ok(Self(at))
+    }
+}
+
+impl FromStr for Droplet {
+    type Err = ParseError<CubeError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let cubes: Vec<Cube> = parse::lines(v)?;
+        Ok(Self(cubes.into_iter().map(|x| x.0).collect()))
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 18;
+    const TITLE: &'static str = "Boiling Boulders";
+
+    type Parsed = Droplet;
+    type Error = ParseError<CubeError>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(droplet: Self::Parsed) -> Self::One {
+        droplet.0.surface()
+    }
+    fn part_two(droplet: Self::Parsed) -> Self::Two {
+        droplet.0.exterior()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{geometry::Point3, parse::Span, solution::Solution};
+
+    use super::{CubeError, Droplet, Puzzle};
+
+    const INPUT: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5";
+
+    #[test]
+    fn parse() {
+        let droplet = INPUT.parse::<Droplet>().unwrap();
+        assert_eq!(droplet.0.len(), 13);
+        assert!(droplet.0.contains(&Point3::new(2, 2, 6)));
+        assert_eq!(
+            droplet.0.bounds(),
+            Some((Point3::new(1, 1, 1), Point3::new(3, 3, 6)))
+        );
+    }
+
+    #[test]
+    fn surface() {
+        let droplet = "1,1,1\n2,1,1".parse::<Droplet>().unwrap();
+        assert_eq!(droplet.0.surface(), 10);
+        let droplet = INPUT.parse::<Droplet>().unwrap();
+        assert_eq!(droplet.0.surface(), 64);
+        assert_eq!(droplet.0.exterior(), 58);
+        assert!(!droplet.0.outside().contains(&Point3::new(2, 2, 5)));
+    }
+
+    #[test]
+    fn solution() {
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), 64);
+        assert_eq!(Puzzle::part_two(Puzzle::parse(INPUT).unwrap()), 58);
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT
+            .replace("2,1,5", "2,x,5")
+            .parse::<Droplet>()
+            .unwrap_err();
+        assert_eq!(err.kind, CubeError::InvalidCoordinate("x".into()));
+        assert_eq!(err.span, Span::new(12, 3, 1));
+        let err = "2,2".parse::<Droplet>().unwrap_err();
+        assert_eq!(err.kind, CubeError::MissingCoordinate);
+        assert_eq!(err.span, Span::new(1, 4, 1));
+        let err = "2,2,2,2".parse::<Droplet>().unwrap_err();
+        assert_eq!(err.kind, CubeError::TooManyCoordinates("2".into()));
+        assert_eq!(err.span, Span::new(1, 7, 1));
+    }
+}
@@ -0,0 +1,407 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    diagnostics::Grammar,
+    parallel,
+    parse::{ParseError, Span},
+    solution::Solution,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+}
+
+impl Resource {
+    pub const ALL: [Self; 4] = [Self::Ore, Self::Clay, Self::Obsidian, Self::Geode];
+}
+
+impl FromStr for Resource {
+    type Err = String;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        match v {
+            "ore" => Ok(Self::Ore),
+            "clay" => Ok(Self::Clay),
+            "obsidian" => Ok(Self::Obsidian),
+            "geode" => Ok(Self::Geode),
+            _ => Err(v.to_string()),
+        }
+    }
+}
+
+impl Display for Resource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Ore => "ore",
+            Self::Clay => "clay",
+            Self::Obsidian => "obsidian",
+            Self::Geode => "geode",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// amount of each resource, indexed by [`Resource`]
+type Amounts = [u32; 4];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blueprint {
+    pub id: u32,
+    /// cost of each robot, both indexed by [`Resource`]
+    pub costs: [Amounts; 4],
+}
+
+/// robots and resources collected so far, with minutes left
+#[derive(Debug, Clone, Copy)]
+struct State {
+    minutes: u32,
+    robots: Amounts,
+    stock: Amounts,
+}
+
+impl Blueprint {
+    /// most robots worth having for each resource, as only one robot can be built per minute
+    fn useful(&self) -> Amounts {
+        let mut useful = [u32::MAX; 4];
+        for resource in [Resource::Ore, Resource::Clay, Resource::Obsidian] {
+            let idx = resource as usize;
+            useful[idx] = self.costs.iter().map(|x| x[idx]).max().unwrap_or_default();
+        }
+        useful
+    }
+    /// minutes to wait until `robot` can be built, if ever
+    fn wait(&self, state: &State, robot: Resource) -> Option<u32> {
+        Resource::ALL
+            .iter()
+            .map(|x| *x as usize)
+            .map(|x| {
+                let missing = self.costs[robot as usize][x].saturating_sub(state.stock[x]);
+                match (missing, state.robots[x]) {
+                    (0, _) => Some(0),
+                    (_, 0) => None,
+                    (missing, robots) => Some(missing.div_ceil(robots)),
+                }
+            })
+            .try_fold(0, |acc, x| Some(acc.max(x?)))
+    }
+    /// depth first over which robot to build next, skipping the minutes spent waiting for it,
+    /// and leaving out branches that could not beat `best` even building a geode robot every minute
+    fn search(&self, state: State, useful: &Amounts, best: &mut u32) {
+        let geode = Resource::Geode as usize;
+        let idle = state.stock[geode] + state.robots[geode] * state.minutes;
+        *best = idle.max(*best);
+        let bound = idle + state.minutes * state.minutes.saturating_sub(1) / 2;
+        if bound <= *best {
+            return;
+        }
+        for robot in Resource::ALL.into_iter().rev() {
+            let idx = robot as usize;
+            // enough robots, or enough in stock, to build whatever needs it every minute left
+            let enough = useful[idx].saturating_mul(state.minutes);
+            if state.robots[idx] >= useful[idx]
+                || state.stock[idx] + state.robots[idx] * state.minutes >= enough
+            {
+                continue;
+            }
+            let Some(wait) = self.wait(&state, robot) else {
+                continue;
+            };
+            if wait + 1 >= state.minutes {
+                continue;
+            }
+            let mut next = State {
+                minutes: state.minutes - wait - 1,
+                ..state
+            };
+            for x in 0..4 {
+                next.stock[x] += next.robots[x] * (wait + 1);
+                next.stock[x] -= self.costs[robot as usize][x];
+            }
+            next.robots[robot as usize] += 1;
+            self.search(next, useful, best);
+        }
+    }
+    /// most geodes that can be opened within `minutes`, starting with a single ore robot
+    pub fn geodes(&self, minutes: u32) -> u32 {
+        let start = State {
+            minutes,
+            robots: [1, 0, 0, 0],
+            stock: [0; 4],
+        };
+        let mut best = 0;
+        self.search(start, &self.useful(), &mut best);
+        best
+    }
+    pub fn quality(&self, minutes: u32) -> u32 {
+        self.id * self.geodes(minutes)
+    }
+}
+
+#[derive(Debug)]
+pub struct Blueprints(pub Vec<Blueprint>);
+
+impl Blueprints {
+    /// most geodes for each blueprint, evaluated in parallel but in the order of the blueprints
+    pub fn geodes(&self, minutes: u32) -> Vec<u32> {
+        parallel::map(&self.0, |x| x.geodes(minutes))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlueprintError {
+    Expected(&'static str),
+    InvalidNumber(String),
+    UnknownResource(String),
+    MissingRobot(Resource),
+    DuplicateRobot(Resource),
+}
+
+impl Display for BlueprintError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Expected(v) => write!(f, "expected {v}"),
+            Self::InvalidNumber(v) => write!(f, "expected a number, found `{v}`"),
+            Self::UnknownResource(v) => write!(f, "expected a resource, found `{v}`"),
+            Self::MissingRobot(v) => write!(f, "missing the cost of a {v} robot"),
+            Self::DuplicateRobot(v) => write!(f, "the cost of a {v} robot is given twice"),
+        }
+    }
+}
+
+impl Grammar for BlueprintError {
+    fn expected(&self) -> &'static str {
+        "Blueprint <n>: Each <resource> robot costs <n> <resource>[ and <n> <resource>]. ..."
+    }
+    fn hint(&self) -> Option<String> {
+        match self {
+            Self::UnknownResource(_) | Self::MissingRobot(_) => {
+                Some("resources are ore, clay, obsidian and geode".into())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// number where `token` is a slice of `text`
+fn number(text: &str, token: &str) -> Result<u32, ParseError<BlueprintError>> {
+    token.parse().map_err(|_| {
+        ParseError::new(
+            BlueprintError::InvalidNumber(token.to_string()),
+            Span::of(text, token),
+        )
+    })
+}
+
+/// resource where `token` is a slice of `text`
+fn resource(text: &str, token: &str) -> Result<Resource, ParseError<BlueprintError>> {
+    token
+        .parse()
+        .map_err(|e| ParseError::new(BlueprintError::UnknownResource(e), Span::of(text, token)))
+}
+
+/// `Each <resource> robot costs <n> <resource>[ and <n> <resource>]`, where `sentence` is a slice of `text`
+fn robot(text: &str, sentence: &str) -> Result<(Resource, Amounts), ParseError<BlueprintError>> {
+    let expected =
+        |what, at: &str| ParseError::new(BlueprintError::Expected(what), Span::of(text, at));
+    let rest = sentence
+        .strip_prefix("Each ")
+        .ok_or_else(|| expected("`Each `", sentence))?;
+    let (kind, rest) = rest
+        .split_once(" robot costs ")
+        .ok_or_else(|| expected("` robot costs `", rest))?;
+    let mut cost = [0; 4];
+    for part in rest.split(" and ") {
+        let (amount, of) = part
+            .split_once(' ')
+            .ok_or_else(|| expected("<n> <resource>", part))?;
+        cost[resource(text, of)? as usize] += number(text, amount)?;
+    }
+    Ok((resource(text, kind)?, cost))
+}
+
+/// blueprint where `chunk` is a slice of `text`, which may span several lines
+fn blueprint(text: &str, chunk: &str) -> Result<Blueprint, ParseError<BlueprintError>> {
+    let chunk = chunk.trim_end();
+    let end = &chunk[chunk.len()..];
+    let rest = chunk.strip_prefix("Blueprint ").ok_or_else(|| {
+        ParseError::new(
+            BlueprintError::Expected("`Blueprint `"),
+            Span::of(text, chunk.split_whitespace().next().unwrap_or(chunk)),
+        )
+    })?;
+    let (id, rest) = rest
+        .split_once(':')
+        .ok_or_else(|| ParseError::new(BlueprintError::Expected("`:`"), Span::of(text, end)))?;
+    let mut costs: [Option<Amounts>; 4] = [None; 4];
+    for sentence in rest.split('.').map(str::trim).filter(|x| !x.is_empty()) {
+        let (kind, cost) = robot(text, sentence)?;
+        if costs[kind as usize].replace(cost).is_some() {
+            return Err(ParseError::new(
+                BlueprintError::DuplicateRobot(kind),
+                Span::of(text, sentence),
+            ));
+        }
+    }
+    let mut missing = Resource::ALL
+        .into_iter()
+        .filter(|x| costs[*x as usize].is_none());
+    if let Some(kind) = missing.next() {
+        return Err(ParseError::new(
+            BlueprintError::MissingRobot(kind),
+            Span::of(text, end),
+        ));
+    }
+    Ok(Blueprint {
+        id: number(text, id)?,
+        costs: costs.map(Option::unwrap_or_default),
+    })
+}
+
+impl FromStr for Blueprint {
+    type Err = ParseError<BlueprintError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        blueprint(v, v)
+    }
+}
+
+impl FromStr for Blueprints {
+    type Err = ParseError<BlueprintError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        // the puzzle text wraps blueprints over several lines, so split on their headers instead
+        let starts: Vec<_> = v.match_indices("Blueprint ").map(|(x, _)| x).collect();
+        let first = starts.first().copied().unwrap_or(v.len());
+        if !v[..first].trim().is_empty() {
+            return Err(blueprint(v, v[..first].trim_start()).unwrap_err());
+        }
+        starts
+            .iter()
+            .zip(starts.iter().skip(1).chain([&v.len()]))
+            .map(|(&start, &end)| blueprint(v, &v[start..end]))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 19;
+    const TITLE: &'static str = "Not Enough Minerals";
+
+    type Parsed = Blueprints;
+    type Error = ParseError<BlueprintError>;
+    type One = u32;
+    type Two = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(blueprints: Self::Parsed) -> Self::One {
+        blueprints
+            .geodes(24)
+            .iter()
+            .zip(blueprints.0.iter())
+            .map(|(geodes, blueprint)| blueprint.id * geodes)
+            .sum()
+    }
+    fn part_two(mut blueprints: Self::Parsed) -> Self::Two {
+        blueprints.0.truncate(3);
+        blueprints.geodes(32).iter().product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse::Span, solution::Solution};
+
+    use super::{Blueprint, BlueprintError, Blueprints, Puzzle, Resource};
+
+    const INPUT: &str = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+
+    #[test]
+    fn parse() {
+        let blueprints = INPUT.parse::<Blueprints>().unwrap();
+        assert_eq!(blueprints.0.len(), 2);
+        assert_eq!(
+            blueprints.0[0],
+            Blueprint {
+                id: 1,
+                costs: [[4, 0, 0, 0], [2, 0, 0, 0], [3, 14, 0, 0], [2, 0, 7, 0]]
+            }
+        );
+    }
+
+    #[test]
+    fn geodes() {
+        let blueprints = INPUT.parse::<Blueprints>().unwrap();
+        assert_eq!(blueprints.0[0].geodes(24), 9);
+        assert_eq!(blueprints.0[1].quality(24), 24);
+        assert_eq!(blueprints.geodes(24), vec![9, 12]);
+        assert_eq!(blueprints.geodes(32), vec![56, 62]);
+        assert_eq!(blueprints.0[0].geodes(0), 0);
+    }
+
+    #[test]
+    fn solution() {
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), 33);
+        assert_eq!(Puzzle::part_two(Puzzle::parse(INPUT).unwrap()), 56 * 62);
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT
+            .replace("costs 2 ore. Each obsidian", "costs x ore. Each obsidian")
+            .parse::<Blueprints>()
+            .unwrap_err();
+        assert_eq!(err.kind, BlueprintError::InvalidNumber("x".into()));
+        assert_eq!(err.span, Span::new(1, 64, 1));
+        let err = INPUT
+            .replace("7 obsidian", "7 diamond")
+            .parse::<Blueprints>()
+            .unwrap_err();
+        assert_eq!(err.kind, BlueprintError::UnknownResource("diamond".into()));
+        assert_eq!(err.span, Span::new(1, 151, 7));
+        let err = "Blueprint 3: Each ore robot costs 4 ore."
+            .parse::<Blueprint>()
+            .unwrap_err();
+        assert_eq!(err.kind, BlueprintError::MissingRobot(Resource::Clay));
+        let err = "Blueprint 3: Each ore robot costs 4 ore. Each ore robot costs 2 ore."
+            .parse::<Blueprint>()
+            .unwrap_err();
+        assert_eq!(err.kind, BlueprintError::DuplicateRobot(Resource::Ore));
+        assert_eq!(err.span, Span::new(1, 42, 26));
+        let err = "Blueprint 3 Each".parse::<Blueprint>().unwrap_err();
+        assert_eq!(err.kind, BlueprintError::Expected("`:`"));
+        let err = "garbage\nBlueprint 3:".parse::<Blueprints>().unwrap_err();
+        assert_eq!(err.kind, BlueprintError::Expected("`Blueprint `"));
+        assert_eq!(err.span, Span::new(1, 1, 7));
+    }
+
+    #[test]
+    fn wrapped() {
+        let wrapped = "Blueprint 1:
+  Each ore robot costs 4 ore.
+  Each clay robot costs 2 ore.
+  Each obsidian robot costs 3 ore and 14 clay.
+  Each geode robot costs 2 ore and 7 obsidian.
+
+Blueprint 2:
+  Each ore robot costs 2 ore.
+  Each clay robot costs 3 ore.
+  Each obsidian robot costs 3 ore and 8 clay.
+  Each geode robot costs 3 ore and 12 obsidian.
+";
+        let blueprints = wrapped.parse::<Blueprints>().unwrap();
+        assert_eq!(blueprints.0, INPUT.parse::<Blueprints>().unwrap().0);
+        let err = wrapped
+            .replace("8 clay", "8 mud")
+            .parse::<Blueprints>()
+            .unwrap_err();
+        assert_eq!(err.kind, BlueprintError::UnknownResource("mud".into()));
+        assert_eq!(err.span, Span::new(10, 41, 3));
+    }
+}
@@ -1,4 +1,10 @@
-#![allow(dead_code)]
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    diagnostics::Grammar,
+    parse::{self, ParseError, Span},
+    solution::Solution,
+};
 
 pub enum Side {
     Myself,
@@ -129,40 +135,83 @@ impl Score for Round {
     }
 }
 
+#[derive(Debug)]
 pub struct Strategy(Vec<Round>);
 
-impl From<char> for Game {
-    fn from(v: char) -> Self {
-        match v {
-            'A' | 'X' => Game::Rock,
-            'B' | 'Y' => Game::Paper,
-            'C' | 'Z' => Game::Scissors,
-            _ => panic!("invalid code"),
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StrategyError {
+    UnknownCode(String),
+    MissingCode,
+    TrailingCode(String),
+}
+
+impl Display for StrategyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownCode(v) => write!(f, "unknown code `{v}`"),
+            Self::MissingCode => write!(f, "missing code"),
+            Self::TrailingCode(v) => write!(f, "round should only contain 2 games, found `{v}`"),
         }
     }
 }
 
-impl From<&str> for Round {
-    fn from(v: &str) -> Self {
-        let mut chars = v.trim().chars();
-        let opponent: Game = chars.next().unwrap().into();
-        chars.next(); // accounts for the space
-        let myself: Game = chars.next().unwrap().into();
-        if let Some(_) = chars.next() {
-            panic!("round should only contain 2 games");
+impl Grammar for StrategyError {
+    fn expected(&self) -> &'static str {
+        "<A|B|C> <X|Y|Z>"
+    }
+    fn hint(&self) -> Option<String> {
+        match self {
+            Self::UnknownCode(_) => {
+                Some("opponent plays A, B or C, and the strategy answers X, Y or Z".into())
+            }
+            _ => None,
         }
-        Round { myself, opponent }
     }
 }
 
-impl From<&str> for Strategy {
-    fn from(v: &str) -> Self {
-        let mut strategy = vec![];
-        let lines = v.lines();
-        for line in lines {
-            strategy.push(line.trim().into());
+impl TryFrom<char> for Game {
+    type Error = StrategyError;
+    fn try_from(v: char) -> Result<Self, Self::Error> {
+        match v {
+            'A' | 'X' => Ok(Game::Rock),
+            'B' | 'Y' => Ok(Game::Paper),
+            'C' | 'Z' => Ok(Game::Scissors),
+            _ => Err(StrategyError::UnknownCode(v.to_string())),
         }
-        Strategy(strategy)
+    }
+}
+
+fn game(line: &str, code: Option<&str>) -> Result<Game, ParseError<StrategyError>> {
+    let code =
+        code.ok_or_else(|| ParseError::new(StrategyError::MissingCode, Span::after(line)))?;
+    let mut chars = code.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Game::try_from(c),
+        _ => Err(StrategyError::UnknownCode(code.to_string())),
+    }
+    .map_err(|kind| ParseError::new(kind, Span::of(line, code)))
+}
+
+impl FromStr for Round {
+    type Err = ParseError<StrategyError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let mut codes = v.split_whitespace();
+        let opponent = game(v, codes.next())?;
+        let myself = game(v, codes.next())?;
+        if let Some(code) = codes.next() {
+            return Err(ParseError::new(
+                StrategyError::TrailingCode(code.to_string()),
+                Span::of(v, code),
+            ));
+        }
+        Ok(Round { myself, opponent })
+    }
+}
+
+impl FromStr for Strategy {
+    type Err = ParseError<StrategyError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        Ok(Strategy(parse::lines(v)?))
     }
 }
 
@@ -186,25 +235,50 @@ impl Strategy {
     }
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+
+    type Parsed = Strategy;
+    type Error = ParseError<StrategyError>;
+    type One = u32;
+    type Two = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(strategy: Self::Parsed) -> Self::One {
+        strategy.score()
+    }
+    fn part_two(strategy: Self::Parsed) -> Self::Two {
+        strategy.cheat().score()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::day_2::{outcome, Outcome, Score};
+    use crate::{
+        day_2::{outcome, Outcome, Score},
+        parse::Span,
+    };
 
-    use super::{Game, Strategy};
+    use super::{Game, Strategy, StrategyError};
 
     #[test]
     fn strategy() {
         let s = "A Y
       B X
       C Z";
-        let strategy = Strategy::from(s);
-        assert_eq!(strategy.0.get(0).unwrap().opponent, Game::Rock);
-        assert_eq!(strategy.0.get(0).unwrap().myself, Game::Paper);
+        let strategy: Strategy = s.parse().unwrap();
+        assert_eq!(strategy.0.first().unwrap().opponent, Game::Rock);
+        assert_eq!(strategy.0.first().unwrap().myself, Game::Paper);
         assert_eq!(strategy.0.get(1).unwrap().opponent, Game::Paper);
         assert_eq!(strategy.0.get(1).unwrap().myself, Game::Rock);
         assert_eq!(strategy.0.get(2).unwrap().opponent, Game::Scissors);
         assert_eq!(strategy.0.get(2).unwrap().myself, Game::Scissors);
-        assert_eq!(outcome(strategy.0.get(0).unwrap()), Outcome::Win);
+        assert_eq!(outcome(strategy.0.first().unwrap()), Outcome::Win);
         assert_eq!(outcome(strategy.0.get(1).unwrap()), Outcome::Lose);
         assert_eq!(outcome(strategy.0.get(2).unwrap()), Outcome::Draw);
         assert_eq!(strategy.score(), 15);
@@ -215,17 +289,28 @@ mod tests {
         let s = "A Y
       B X
       C Z";
-        let strategy = Strategy::from(s);
+        let strategy: Strategy = s.parse().unwrap();
         let strategy = strategy.cheat();
-        assert_eq!(strategy.0.get(0).unwrap().opponent, Game::Rock);
-        assert_eq!(strategy.0.get(0).unwrap().myself, Game::Rock);
+        assert_eq!(strategy.0.first().unwrap().opponent, Game::Rock);
+        assert_eq!(strategy.0.first().unwrap().myself, Game::Rock);
         assert_eq!(strategy.0.get(1).unwrap().opponent, Game::Paper);
         assert_eq!(strategy.0.get(1).unwrap().myself, Game::Rock);
         assert_eq!(strategy.0.get(2).unwrap().opponent, Game::Scissors);
         assert_eq!(strategy.0.get(2).unwrap().myself, Game::Rock);
-        assert_eq!(outcome(strategy.0.get(0).unwrap()), Outcome::Draw);
+        assert_eq!(outcome(strategy.0.first().unwrap()), Outcome::Draw);
         assert_eq!(outcome(strategy.0.get(1).unwrap()), Outcome::Lose);
         assert_eq!(outcome(strategy.0.get(2).unwrap()), Outcome::Win);
         assert_eq!(strategy.score(), 12);
     }
+
+    #[test]
+    fn invalid() {
+        let err = "A Y\nB W".parse::<Strategy>().unwrap_err();
+        assert_eq!(err.kind, StrategyError::UnknownCode("W".into()));
+        assert_eq!(err.span, Span::new(2, 3, 1));
+        let err = "A Y\nB".parse::<Strategy>().unwrap_err();
+        assert_eq!(err.kind, StrategyError::MissingCode);
+        let err = "A Y Z".parse::<Strategy>().unwrap_err();
+        assert_eq!(err.kind, StrategyError::TrailingCode("Z".into()));
+    }
 }
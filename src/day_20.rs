@@ -0,0 +1,183 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    circular::Circular,
+    diagnostics::Grammar,
+    parse::{self, ParseError, Span},
+    solution::Solution,
+};
+
+/// key every number is multiplied by before decrypting
+pub const DECRYPTION_KEY: i64 = 811_589_153;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Number(pub i64);
+
+/// encrypted numbers, in their original order
+#[derive(Debug)]
+pub struct File(pub Vec<i64>);
+
+impl File {
+    /// numbers multiplied by `key`, each moved by its own value once per round in the original order
+    pub fn mix(&self, key: i64, rounds: usize) -> Circular<i64> {
+        let mut list = Circular::new(self.0.iter().map(|x| x * key).collect());
+        for _ in 0..rounds {
+            for id in 0..list.len() {
+                list.shift(id, *list.get(id));
+            }
+        }
+        list
+    }
+    /// sum of the 1000th, 2000th and 3000th numbers after 0 once mixed
+    pub fn grove(&self, key: i64, rounds: usize) -> i64 {
+        let list = self.mix(key, rounds);
+        let zero = list
+            .values()
+            .iter()
+            .position(|x| *x == 0)
+            .expect("a zero in the file");
+        [1000, 2000, 3000]
+            .iter()
+            .map(|x| list.after(zero, *x))
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileError {
+    InvalidNumber(String),
+    MissingZero,
+}
+
+impl Display for FileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidNumber(v) => write!(f, "expected a number, found `{v}`"),
+            Self::MissingZero => write!(f, "expected a 0 in the file"),
+        }
+    }
+}
+
+impl Grammar for FileError {
+    fn expected(&self) -> &'static str {
+        "<n>, one per line, with at least one 0"
+    }
+    fn hint(&self) -> Option<String> {
+        match self {
+            Self::MissingZero => Some("grove coordinates are counted from the 0".into()),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Number {
+    type Err = ParseError<FileError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let token = v.trim();
+        token.parse().map(Self).map_err(|_| {
+            ParseError::new(
+                FileError::InvalidNumber(token.to_string()),
+                Span::of(v, token),
+            )
+        })
+    }
+}
+
+impl FromStr for File {
+    type Err = ParseError<FileError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let numbers: Vec<Number> = parse::lines(v)?;
+        match numbers.contains(&Number(0)) {
+            true => Ok(Self(numbers.into_iter().map(|x| x.0).collect())),
+            false => Err(ParseError::new(FileError::MissingZero, Span::new(1, 1, 1))),
+        }
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 20;
+    const TITLE: &'static str = "Grove Positioning System";
+
+    type Parsed = File;
+    type Error = ParseError<FileError>;
+    type One = i64;
+    type Two = i64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(file: Self::Parsed) -> Self::One {
+        file.grove(1, 1)
+    }
+    fn part_two(file: Self::Parsed) -> Self::Two {
+        file.grove(DECRYPTION_KEY, 10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse::Span, solution::Solution};
+
+    use super::{File, FileError, Puzzle, DECRYPTION_KEY};
+
+    const INPUT: &str = "1
+2
+-3
+3
+-2
+0
+4";
+
+    /// numbers once mixed, starting from 0
+    fn from_zero(file: &File, key: i64, rounds: usize) -> Vec<i64> {
+        let list = file.mix(key, rounds);
+        let mut numbers: Vec<i64> = list.iter().copied().collect();
+        let zero = numbers.iter().position(|x| *x == 0).unwrap();
+        numbers.rotate_left(zero);
+        numbers
+    }
+
+    #[test]
+    fn parse() {
+        let file = INPUT.parse::<File>().unwrap();
+        assert_eq!(file.0, vec![1, 2, -3, 3, -2, 0, 4]);
+    }
+
+    #[test]
+    fn mix() {
+        let file = INPUT.parse::<File>().unwrap();
+        assert_eq!(from_zero(&file, 1, 1), vec![0, 3, -2, 1, 2, -3, 4]);
+        assert_eq!(file.grove(1, 1), 3);
+        assert_eq!(
+            from_zero(&file, DECRYPTION_KEY, 10),
+            vec![
+                0,
+                -2434767459,
+                1623178306,
+                3246356612,
+                -1623178306,
+                2434767459,
+                811589153
+            ]
+        );
+        let duplicates = "0\n1\n1\n-1".parse::<File>().unwrap();
+        assert_eq!(from_zero(&duplicates, 1, 1), vec![0, 1, -1, 1]);
+    }
+
+    #[test]
+    fn solution() {
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), 3);
+        assert_eq!(Puzzle::part_two(Puzzle::parse(INPUT).unwrap()), 1623178306);
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT.replace("-2", "-x").parse::<File>().unwrap_err();
+        assert_eq!(err.kind, FileError::InvalidNumber("-x".into()));
+        assert_eq!(err.span, Span::new(5, 1, 2));
+        let err = INPUT.replace('0', "5").parse::<File>().unwrap_err();
+        assert_eq!(err.kind, FileError::MissingZero);
+    }
+}
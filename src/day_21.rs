@@ -0,0 +1,396 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    rc::Rc,
+    str::FromStr,
+};
+
+use crate::{
+    diagnostics::Grammar,
+    expression::{Expr, Operator},
+    parse::{ParseError, Span},
+    solution::{Solution, Unsolvable},
+};
+
+/// monkey whose number is the answer
+pub const ROOT: &str = "root";
+/// you, shouting a number of your own
+pub const HUMAN: &str = "humn";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Job {
+    Number(i64),
+    Operation(String, Operator, String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monkey {
+    pub name: String,
+    pub job: Job,
+}
+
+/// every monkey's job, by name
+#[derive(Debug)]
+pub struct Troop(pub HashMap<String, Job>);
+
+impl Troop {
+    /// what `name` yells, with `unknown` left as a variable rather than its own number
+    pub fn expression(&self, name: &str, unknown: Option<&str>) -> Expr {
+        Rc::unwrap_or_clone(self.build(name, unknown, &mut HashMap::new()))
+    }
+    /// `expression`, building what each monkey yells once into `built`, folded to a number
+    /// when it does not wait on `unknown`, and shared by every monkey waiting on it
+    fn build(
+        &self,
+        name: &str,
+        unknown: Option<&str>,
+        built: &mut HashMap<String, Rc<Expr>>,
+    ) -> Rc<Expr> {
+        if let Some(expr) = built.get(name) {
+            return Rc::clone(expr);
+        }
+        let expr = Rc::new(match &self.0[name] {
+            _ if unknown == Some(name) => Expr::Variable(name.to_string()),
+            Job::Number(v) => Expr::Number(*v),
+            Job::Operation(left, operator, right) => Expr::fold(
+                *operator,
+                self.build(left, unknown, built),
+                self.build(right, unknown, built),
+            ),
+        });
+        built.insert(name.to_string(), Rc::clone(&expr));
+        expr
+    }
+    /// number yelled by the root monkey
+    pub fn root(&self) -> Option<i64> {
+        self.expression(ROOT, None).evaluate(&|_| None)
+    }
+    /// number to yell for both sides of the root monkey's job to be equal
+    pub fn yell(&self) -> Option<i64> {
+        let Job::Operation(left, _, right) = &self.0[ROOT] else {
+            return None;
+        };
+        let left = self.expression(left, Some(HUMAN));
+        let right = self.expression(right, Some(HUMAN));
+        Expr::binary(Operator::Sub, left, right).solve(0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobError {
+    Expected(&'static str),
+    InvalidName(String),
+    InvalidNumber(String),
+    UnknownOperator(String),
+    UnknownMonkey(String),
+    DuplicateMonkey(String),
+    MissingMonkey(&'static str),
+    CyclicJob(String),
+}
+
+impl Display for JobError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Expected(v) => write!(f, "expected {v}"),
+            Self::InvalidName(v) => write!(f, "expected a monkey name, found `{v}`"),
+            Self::InvalidNumber(v) => write!(f, "expected a number, found `{v}`"),
+            Self::UnknownOperator(v) => write!(f, "expected `+`, `-`, `*` or `/`, found `{v}`"),
+            Self::UnknownMonkey(v) => write!(f, "no monkey named `{v}`"),
+            Self::DuplicateMonkey(v) => write!(f, "monkey `{v}` already has a job"),
+            Self::MissingMonkey(v) => write!(f, "expected a monkey named `{v}`"),
+            Self::CyclicJob(v) => write!(f, "monkey `{v}` waits on its own number"),
+        }
+    }
+}
+
+impl Grammar for JobError {
+    fn expected(&self) -> &'static str {
+        "<name>: <n>
+<name>: <name> <+|-|*|/> <name>"
+    }
+    fn hint(&self) -> Option<String> {
+        match self {
+            Self::MissingMonkey(_) => Some(format!("jobs should include `{ROOT}` and `{HUMAN}`")),
+            Self::CyclicJob(_) => Some("every monkey should eventually wait on numbers".into()),
+            _ => None,
+        }
+    }
+}
+
+/// monkey name, where `token` is a slice of `line`
+fn name(line: &str, token: &str) -> Result<String, ParseError<JobError>> {
+    match !token.is_empty() && token.chars().all(|x| x.is_ascii_lowercase()) {
+        true => Ok(token.to_string()),
+        false => Err(ParseError::new(
+            JobError::InvalidName(token.to_string()),
+            Span::of(line, token),
+        )),
+    }
+}
+
+impl FromStr for Monkey {
+    type Err = ParseError<JobError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let line = v.trim_end();
+        let (monkey, job) = line
+            .split_once(": ")
+            .ok_or_else(|| ParseError::new(JobError::Expected("`: `"), Span::after(line)))?;
+        let parts: Vec<&str> = job.split(' ').collect();
+        let job = match parts.as_slice() {
+            [left, operator, right] => Job::Operation(
+                name(line, left)?,
+                operator.parse().map_err(|e| {
+                    ParseError::new(JobError::UnknownOperator(e), Span::of(line, operator))
+                })?,
+                name(line, right)?,
+            ),
+            [number] => Job::Number(number.parse().map_err(|_| {
+                ParseError::new(
+                    JobError::InvalidNumber(number.to_string()),
+                    Span::of(line, number),
+                )
+            })?),
+            _ => {
+                return Err(ParseError::new(
+                    JobError::Expected("<n> or <name> <operator> <name>"),
+                    Span::of(line, job),
+                ))
+            }
+        };
+        Ok(Self {
+            name: name(line, monkey)?,
+            job,
+        })
+    }
+}
+
+/// first monkey found waiting on its own number, directly or not
+fn cyclic(jobs: &HashMap<String, Job>) -> Option<String> {
+    let mut done: HashSet<&str> = HashSet::new();
+    for start in jobs.keys() {
+        let mut path: Vec<&str> = vec![];
+        let mut stack: Vec<(&str, bool)> = vec![(start, false)];
+        while let Some((name, leaving)) = stack.pop() {
+            if leaving {
+                path.pop();
+                done.insert(name);
+                continue;
+            }
+            if done.contains(name) {
+                continue;
+            }
+            if path.contains(&name) {
+                return Some(name.to_string());
+            }
+            path.push(name);
+            stack.push((name, true));
+            if let Some(Job::Operation(left, _, right)) = jobs.get(name) {
+                stack.push((left, false));
+                stack.push((right, false));
+            }
+        }
+    }
+    None
+}
+
+impl FromStr for Troop {
+    type Err = ParseError<JobError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let mut jobs: HashMap<String, Job> = HashMap::new();
+        let mut lines: HashMap<String, (usize, &str)> = HashMap::new();
+        for (idx, line) in v.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let monkey = line.parse::<Monkey>().map_err(|e| e.shift(idx))?;
+            if jobs.insert(monkey.name.clone(), monkey.job).is_some() {
+                return Err(ParseError::new(
+                    JobError::DuplicateMonkey(monkey.name.clone()),
+                    Span::of(line, &line[..monkey.name.len()]).shift(idx),
+                ));
+            }
+            lines.insert(monkey.name, (idx, line));
+        }
+        for (monkey, job) in jobs.iter() {
+            let Job::Operation(left, _, right) = job else {
+                continue;
+            };
+            if let Some(unknown) = [left, right].into_iter().find(|x| !jobs.contains_key(*x)) {
+                let (idx, line) = lines[monkey];
+                let job = &line[monkey.len() + 2..];
+                let token = job.split(' ').find(|x| x == unknown).unwrap_or(job);
+                return Err(ParseError::new(
+                    JobError::UnknownMonkey(unknown.clone()),
+                    Span::of(line, token).shift(idx),
+                ));
+            }
+        }
+        if let Some(missing) = [ROOT, HUMAN].into_iter().find(|x| !jobs.contains_key(*x)) {
+            return Err(ParseError::new(
+                JobError::MissingMonkey(missing),
+                Span::new(1, 1, 1),
+            ));
+        }
+        if let Some(monkey) = cyclic(&jobs) {
+            let (idx, line) = lines[&monkey];
+            return Err(ParseError::new(
+                JobError::CyclicJob(monkey.clone()),
+                Span::of(line, &line[..monkey.len()]).shift(idx),
+            ));
+        }
+        Ok(Self(jobs))
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 21;
+    const TITLE: &'static str = "Monkey Math";
+
+    type Parsed = Troop;
+    type Error = ParseError<JobError>;
+    type One = Result<i64, Unsolvable>;
+    type Two = Result<i64, Unsolvable>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(troop: Self::Parsed) -> Self::One {
+        troop
+            .root()
+            .ok_or_else(|| Unsolvable::new("jobs overflow or divide by zero on the way to root"))
+    }
+    fn part_two(troop: Self::Parsed) -> Self::Two {
+        troop
+            .yell()
+            .ok_or_else(|| Unsolvable::new("no whole number makes both sides of root equal"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::{
+        expression::{Expr, Operator},
+        parse::Span,
+        solution::Solution,
+    };
+
+    use super::{Job, JobError, Monkey, Puzzle, Troop, HUMAN};
+
+    const INPUT: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
+
+    #[test]
+    fn parse() {
+        let troop = INPUT.parse::<Troop>().unwrap();
+        assert_eq!(troop.0.len(), 15);
+        assert_eq!(troop.0["hmdt"], Job::Number(32));
+        assert_eq!(
+            "root: pppw + sjmn".parse::<Monkey>().unwrap().job,
+            Job::Operation("pppw".into(), Operator::Add, "sjmn".into())
+        );
+    }
+
+    #[test]
+    fn expression() {
+        let troop = INPUT.parse::<Troop>().unwrap();
+        assert_eq!(
+            troop.expression("pppw", Some(HUMAN)).simplify().to_string(),
+            "((4 + (2 * (humn - 3))) / 4)"
+        );
+        assert_eq!(
+            troop.expression("sjmn", Some(HUMAN)).simplify().to_string(),
+            "150"
+        );
+        assert_eq!(troop.root(), Some(152));
+        assert_eq!(troop.yell(), Some(301));
+    }
+
+    #[test]
+    fn shared() {
+        // each monkey waits twice on the next one, down to 40 monkeys deep
+        let name = |depth: usize| "m".repeat(depth);
+        let jobs: Vec<String> = (1..40)
+            .map(|x| format!("{}: {} + {}", name(x), name(x + 1), name(x + 1)))
+            .chain([
+                format!("{}: 1", name(40)),
+                format!("root: {} + humn", name(1)),
+                "humn: 5".into(),
+            ])
+            .collect();
+        let troop = jobs.join("\n").parse::<Troop>().unwrap();
+        assert_eq!(troop.expression("m", Some(HUMAN)), Expr::Number(1 << 39));
+        assert_eq!(troop.root(), Some((1 << 39) + 5));
+        assert_eq!(troop.yell(), Some(1 << 39));
+        // waiting on you twice, through the same monkey built once
+        let troop = "root: mm + m\nm: mm * mm\nmm: humn - dvpt\ndvpt: 3\nhumn: 5"
+            .parse::<Troop>()
+            .unwrap();
+        let Expr::Binary(_, left, right) = troop.expression("m", Some(HUMAN)) else {
+            panic!("expected an operation");
+        };
+        assert!(Rc::ptr_eq(&left, &right));
+        assert_eq!(left.to_string(), "(humn - 3)");
+    }
+
+    #[test]
+    fn solution() {
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), Ok(152));
+        assert_eq!(Puzzle::part_two(Puzzle::parse(INPUT).unwrap()), Ok(301));
+        let zero = Puzzle::parse(&INPUT.replace("lfqf: 4", "lfqf: 0")).unwrap();
+        assert!(Puzzle::part_one(zero).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT
+            .replace("humn - dvpt", "humn % dvpt")
+            .parse::<Troop>()
+            .unwrap_err();
+        assert_eq!(err.kind, JobError::UnknownOperator("%".into()));
+        assert_eq!(err.span, Span::new(5, 12, 1));
+        let err = INPUT
+            .replace("sllz + lgvd", "sllz + lgvx")
+            .parse::<Troop>()
+            .unwrap_err();
+        assert_eq!(err.kind, JobError::UnknownMonkey("lgvx".into()));
+        assert_eq!(err.span, Span::new(3, 14, 4));
+        let err = INPUT
+            .replace("hmdt: 32", "hmdt: x2")
+            .parse::<Troop>()
+            .unwrap_err();
+        assert_eq!(err.kind, JobError::InvalidNumber("x2".into()));
+        let err = INPUT
+            .replace("dbpl: 5", "zczc: 5")
+            .parse::<Troop>()
+            .unwrap_err();
+        assert_eq!(err.kind, JobError::DuplicateMonkey("zczc".into()));
+        assert_eq!(err.span, Span::new(4, 1, 4));
+        let err = INPUT
+            .replace("humn: 5", "humx: 5")
+            .parse::<Troop>()
+            .unwrap_err();
+        assert_eq!(err.kind, JobError::UnknownMonkey("humn".into()));
+        let err = INPUT
+            .replace("hmdt: 32", "hmdt: drzm + zczc")
+            .parse::<Troop>()
+            .unwrap_err();
+        assert!(matches!(err.kind, JobError::CyclicJob(x) if x == "hmdt" || x == "drzm"));
+    }
+}
@@ -0,0 +1,581 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+    str::FromStr,
+};
+
+use crate::{
+    diagnostics::Grammar,
+    geometry::{Direction, Offset, Point, Point3},
+    grid::Grid,
+    parse::{ParseError, Span},
+    solution::{Solution, Unsolvable},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    /// off the map
+    Void,
+    Open,
+    Wall,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Forward(usize),
+    Turn(Turn),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path(pub Vec<Step>);
+
+/// where you stand on the board and which way you face
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Walker {
+    pub at: Point,
+    pub facing: Direction,
+}
+
+impl Walker {
+    pub fn new(at: Point, facing: Direction) -> Self {
+        Self { at, facing }
+    }
+    /// 1000 times the row, 4 times the column, counted from 1, plus the facing
+    pub fn password(&self) -> usize {
+        let facing = match self.facing {
+            Direction::Right => 0,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Up => 3,
+            _ => unreachable!("facing along an axis"),
+        };
+        1000 * (self.at.y as usize + 1) + 4 * (self.at.x as usize + 1) + facing
+    }
+}
+
+/// map of the board, padded with void where lines are short
+#[derive(Debug, Clone)]
+pub struct Board(Grid<Tile>);
+
+impl Board {
+    pub fn tile(&self, at: Point) -> Tile {
+        self.0.get(at).copied().unwrap_or(Tile::Void)
+    }
+    /// leftmost open tile of the top row, facing right
+    pub fn start(&self) -> Option<Walker> {
+        (0..self.0.width() as isize)
+            .map(|x| Point::new(x, 0))
+            .find(|x| self.tile(*x) == Tile::Open)
+            .map(|x| Walker::new(x, Direction::Right))
+    }
+    /// one step ahead, coming back from the opposite side of the row or column past its edge
+    pub fn wrap(&self, walker: Walker) -> Walker {
+        let ahead = walker.at.step(walker.facing);
+        if self.tile(ahead) != Tile::Void {
+            return Walker::new(ahead, walker.facing);
+        }
+        let back = walker.facing.opposite();
+        let mut at = walker.at;
+        while self.tile(at.step(back)) != Tile::Void {
+            at = at.step(back);
+        }
+        Walker::new(at, walker.facing)
+    }
+    /// where following `path` ends, with `next` giving the tile one step ahead
+    pub fn walk(&self, path: &Path, next: impl Fn(Walker) -> Walker) -> Walker {
+        let mut walker = self.start().expect("an open tile on the top row");
+        for step in path.0.iter() {
+            match step {
+                Step::Forward(n) => {
+                    for _ in 0..*n {
+                        let ahead = next(walker);
+                        if self.tile(ahead.at) == Tile::Wall {
+                            break;
+                        }
+                        walker = ahead;
+                    }
+                }
+                Step::Turn(Turn::Left) => walker.facing = walker.facing.turn_left(),
+                Step::Turn(Turn::Right) => walker.facing = walker.facing.turn_right(),
+            }
+        }
+        walker
+    }
+}
+
+/// square of the net, with the directions its right and down on the map point to once folded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Face {
+    corner: Point,
+    /// outward, away from the center of the cube
+    normal: Point3,
+    right: Point3,
+    down: Point3,
+}
+
+impl Face {
+    /// where moving toward `direction` on the map points to once folded
+    fn toward(&self, direction: Direction) -> Point3 {
+        match direction {
+            Direction::Right => self.right,
+            Direction::Left => -self.right,
+            Direction::Down => self.down,
+            Direction::Up => -self.down,
+            _ => unreachable!("facing along an axis"),
+        }
+    }
+    /// face next to this one toward `direction` on the map, once folded along their shared edge
+    fn fold(&self, direction: Direction, corner: Point) -> Self {
+        let normal = self.toward(direction);
+        let (right, down) = match direction {
+            Direction::Right => (-self.normal, self.down),
+            Direction::Left => (self.normal, self.down),
+            Direction::Down => (self.right, -self.normal),
+            Direction::Up => (self.right, self.normal),
+            _ => unreachable!("facing along an axis"),
+        };
+        Self {
+            corner,
+            normal,
+            right,
+            down,
+        }
+    }
+}
+
+/// board folded along the edges of its net, where each face knows its place on the cube
+#[derive(Debug, Clone)]
+pub struct Cube {
+    size: usize,
+    /// faces by their position in the net, in units of faces
+    faces: HashMap<Point, Face>,
+}
+
+impl Cube {
+    /// folds the net of 6 square faces drawn by the board, whichever its layout
+    pub fn fold(board: &Board) -> Result<Self, BoardError> {
+        let tiles = board.0.iter().filter(|(_, x)| **x != Tile::Void).count();
+        let size = ((tiles / 6) as f64).sqrt() as usize;
+        if size == 0 || 6 * size * size != tiles {
+            return Err(BoardError::UnevenFaces);
+        }
+        let blocks = |length: usize| length.div_ceil(size);
+        let mut squares: Vec<Point> = vec![];
+        for y in 0..blocks(board.0.height()) {
+            for x in 0..blocks(board.0.width()) {
+                let corner = Point::new((x * size) as isize, (y * size) as isize);
+                let filled = (0..size * size)
+                    .map(|i| corner + Offset::new((i % size) as isize, (i / size) as isize))
+                    .filter(|x| board.tile(*x) != Tile::Void)
+                    .count();
+                match filled {
+                    0 => {}
+                    n if n == size * size => squares.push(Point::new(x as isize, y as isize)),
+                    _ => return Err(BoardError::UnevenFaces),
+                }
+            }
+        }
+        let first = squares[0];
+        let mut faces = HashMap::from([(
+            first,
+            Face {
+                corner: Point::new(first.x * size as isize, first.y * size as isize),
+                normal: Point3::new(0, 0, -1),
+                right: Point3::new(1, 0, 0),
+                down: Point3::new(0, 1, 0),
+            },
+        )]);
+        let mut queue = VecDeque::from([first]);
+        while let Some(square) = queue.pop_front() {
+            for direction in Direction::ORTHOGONAL {
+                let next = square.step(direction);
+                if !squares.contains(&next) || faces.contains_key(&next) {
+                    continue;
+                }
+                let corner = Point::new(next.x * size as isize, next.y * size as isize);
+                faces.insert(next, faces[&square].fold(direction, corner));
+                queue.push_back(next);
+            }
+        }
+        let folded = faces
+            .values()
+            .all(|x| faces.values().filter(|y| y.normal == x.normal).count() == 1);
+        match faces.len() == squares.len() && folded {
+            true => Ok(Self { size, faces }),
+            false => Err(BoardError::NotANet),
+        }
+    }
+    /// position in the net of the face `at` would be on
+    fn square(&self, at: Point) -> Point {
+        let size = self.size as isize;
+        Point::new(at.x.div_euclid(size), at.y.div_euclid(size))
+    }
+    /// one step ahead, around the edge of the cube onto the next face when past the edge of this one
+    pub fn wrap(&self, walker: Walker) -> Walker {
+        let ahead = walker.at.step(walker.facing);
+        if self.square(ahead) == self.square(walker.at) {
+            return Walker::new(ahead, walker.facing);
+        }
+        let face = &self.faces[&self.square(walker.at)];
+        // tiles in space with the cube twice as large, so that tile centers are integers
+        let n = self.size as isize;
+        let local = walker.at - face.corner;
+        let position = face.normal * n
+            + face.right * (2 * local.x + 1 - n)
+            + face.down * (2 * local.y + 1 - n);
+        let over = face.toward(walker.facing);
+        let position = position + over - face.normal;
+        let next = self
+            .faces
+            .values()
+            .find(|x| x.normal == over)
+            .expect("a face on each side of the cube");
+        let facing = Direction::ORTHOGONAL
+            .into_iter()
+            .find(|x| next.toward(*x) == -face.normal)
+            .expect("an edge shared by both faces");
+        let local = Offset::new(
+            (position.dot(&next.right) + n - 1) / 2,
+            (position.dot(&next.down) + n - 1) / 2,
+        );
+        Walker::new(next.corner + local, facing)
+    }
+}
+
+/// board and path from the monkeys' notes
+#[derive(Debug, Clone)]
+pub struct Notes {
+    pub board: Board,
+    pub path: Path,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardError {
+    Empty,
+    InvalidTile(char),
+    MissingStart,
+    MissingPath,
+    InvalidStep(char),
+    InvalidDistance(String),
+    UnevenFaces,
+    NotANet,
+}
+
+impl Display for BoardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "expected a board"),
+            Self::InvalidTile(c) => write!(f, "expected ` `, `.` or `#`, found `{c}`"),
+            Self::MissingStart => write!(f, "expected an open tile on the top row"),
+            Self::MissingPath => write!(f, "expected a path after the board"),
+            Self::InvalidStep(c) => write!(f, "expected a number, `L` or `R`, found `{c}`"),
+            Self::InvalidDistance(v) => write!(f, "expected a number of tiles, found `{v}`"),
+            Self::UnevenFaces => write!(f, "expected the board to split into 6 square faces"),
+            Self::NotANet => write!(f, "expected the faces to fold into a cube"),
+        }
+    }
+}
+
+impl Grammar for BoardError {
+    fn expected(&self) -> &'static str {
+        "< |.|#>..., one row per line
+<blank line>
+<n|L|R>..."
+    }
+    fn hint(&self) -> Option<String> {
+        match self {
+            Self::UnevenFaces | Self::NotANet => {
+                Some("the board should be the net of a cube, whichever its layout".into())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Board {
+    type Err = ParseError<BoardError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let width = v.lines().map(|x| x.chars().count()).max().unwrap_or(0);
+        let height = v.lines().count();
+        if width == 0 {
+            return Err(ParseError::new(BoardError::Empty, Span::new(1, 1, 1)));
+        }
+        let mut grid = Grid::new(width, height, Tile::Void);
+        for (y, line) in v.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                grid[Point::new(x as isize, y as isize)] = match c {
+                    ' ' => Tile::Void,
+                    '.' => Tile::Open,
+                    '#' => Tile::Wall,
+                    _ => {
+                        return Err(ParseError::new(
+                            BoardError::InvalidTile(c),
+                            Span::new(y + 1, x + 1, 1),
+                        ))
+                    }
+                };
+            }
+        }
+        let board = Self(grid);
+        match board.start() {
+            Some(_) => Ok(board),
+            None => Err(ParseError::new(
+                BoardError::MissingStart,
+                Span::new(1, 1, width),
+            )),
+        }
+    }
+}
+
+impl FromStr for Path {
+    type Err = ParseError<BoardError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let line = v.trim_end();
+        if line.is_empty() {
+            return Err(ParseError::new(BoardError::MissingPath, Span::new(1, 1, 1)));
+        }
+        let mut steps = vec![];
+        let mut digits = 0;
+        let forward = |end: usize, digits: usize| {
+            let token = &line[end - digits..end];
+            token.parse().map(Step::Forward).map_err(|_| {
+                ParseError::new(
+                    BoardError::InvalidDistance(token.to_string()),
+                    Span::of(line, token),
+                )
+            })
+        };
+        for (idx, c) in line.char_indices() {
+            let turn = match c {
+                '0'..='9' => {
+                    digits += 1;
+                    continue;
+                }
+                'L' => Turn::Left,
+                'R' => Turn::Right,
+                _ => {
+                    return Err(ParseError::new(
+                        BoardError::InvalidStep(c),
+                        Span::new(1, line[..idx].chars().count() + 1, 1),
+                    ))
+                }
+            };
+            if digits > 0 {
+                steps.push(forward(idx, digits)?);
+                digits = 0;
+            }
+            steps.push(Step::Turn(turn));
+        }
+        if digits > 0 {
+            steps.push(forward(line.len(), digits)?);
+        }
+        Ok(Self(steps))
+    }
+}
+
+impl FromStr for Notes {
+    type Err = ParseError<BoardError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let (board, path) = v.split_once("\n\n").ok_or_else(|| {
+            ParseError::new(
+                BoardError::MissingPath,
+                Span::new(v.lines().count() + 1, 1, 1),
+            )
+        })?;
+        let lines = board.lines().count() + 1;
+        let board: Board = board.parse()?;
+        let path = path.parse().map_err(|e: ParseError<_>| e.shift(lines))?;
+        Ok(Self { board, path })
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 22;
+    const TITLE: &'static str = "Monkey Map";
+
+    type Parsed = Notes;
+    type Error = ParseError<BoardError>;
+    type One = usize;
+    type Two = Result<usize, Unsolvable>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(notes: Self::Parsed) -> Self::One {
+        let Notes { board, path } = notes;
+        board.walk(&path, |x| board.wrap(x)).password()
+    }
+    fn part_two(notes: Self::Parsed) -> Self::Two {
+        let Notes { board, path } = notes;
+        let cube = Cube::fold(&board).map_err(|e| Unsolvable::new(e.to_string()))?;
+        Ok(board.walk(&path, |x| cube.wrap(x)).password())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        geometry::{Direction, Point},
+        parse::Span,
+        solution::Solution,
+    };
+
+    use super::{Board, BoardError, Cube, Notes, Puzzle, Step, Tile, Turn, Walker};
+
+    const INPUT: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5";
+
+    /// net laid out as in most puzzle inputs, with faces of 2 tiles
+    const NET: &str = "  ....
+  ....
+  ..
+  ..
+....
+....
+..
+..";
+
+    /// every tile on the edge of a face, with the direction leading off it
+    fn edges(board: &Board, size: isize) -> Vec<Walker> {
+        board
+            .0
+            .iter()
+            .filter(|(_, x)| **x != Tile::Void)
+            .flat_map(|(at, _)| {
+                Direction::ORTHOGONAL
+                    .into_iter()
+                    .filter(move |x| {
+                        let ahead = at.step(*x);
+                        ahead.x.div_euclid(size) != at.x / size
+                            || ahead.y.div_euclid(size) != at.y / size
+                    })
+                    .map(move |x| Walker::new(at, x))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse() {
+        let notes = INPUT.parse::<Notes>().unwrap();
+        assert_eq!(notes.board.0.width(), 16);
+        assert_eq!(notes.board.0.height(), 12);
+        assert_eq!(notes.board.tile(Point::new(11, 0)), Tile::Wall);
+        assert_eq!(notes.board.tile(Point::new(12, 0)), Tile::Void);
+        assert_eq!(notes.path.0.len(), 13);
+        assert_eq!(
+            notes.path.0[..2],
+            [Step::Forward(10), Step::Turn(Turn::Right)]
+        );
+        assert_eq!(
+            notes.board.start(),
+            Some(Walker::new(Point::new(8, 0), Direction::Right))
+        );
+    }
+
+    #[test]
+    fn flat() {
+        let notes = INPUT.parse::<Notes>().unwrap();
+        let board = &notes.board;
+        assert_eq!(
+            board.wrap(Walker::new(Point::new(11, 6), Direction::Right)),
+            Walker::new(Point::new(0, 6), Direction::Right)
+        );
+        assert_eq!(
+            board.wrap(Walker::new(Point::new(5, 4), Direction::Up)),
+            Walker::new(Point::new(5, 7), Direction::Up)
+        );
+        let end = board.walk(&notes.path, |x| board.wrap(x));
+        assert_eq!(end, Walker::new(Point::new(7, 5), Direction::Right));
+    }
+
+    #[test]
+    fn fold() {
+        let notes = INPUT.parse::<Notes>().unwrap();
+        let cube = &Cube::fold(&notes.board).unwrap();
+        assert_eq!(
+            cube.wrap(Walker::new(Point::new(11, 5), Direction::Right)),
+            Walker::new(Point::new(14, 8), Direction::Down)
+        );
+        assert_eq!(
+            cube.wrap(Walker::new(Point::new(10, 11), Direction::Down)),
+            Walker::new(Point::new(1, 7), Direction::Up)
+        );
+        assert_eq!(
+            cube.wrap(Walker::new(Point::new(6, 4), Direction::Up)),
+            Walker::new(Point::new(8, 2), Direction::Right)
+        );
+        let end = notes.board.walk(&notes.path, |x| cube.wrap(x));
+        assert_eq!(end, Walker::new(Point::new(6, 4), Direction::Up));
+    }
+
+    #[test]
+    fn nets() {
+        for (board, size) in [(INPUT.split_once("\n\n").unwrap().0, 4), (NET, 2)] {
+            let board = board.parse::<Board>().unwrap();
+            let cube = Cube::fold(&board).unwrap();
+            for walker in edges(&board, size) {
+                // around the edge and back again
+                let over = cube.wrap(walker);
+                let back = cube.wrap(Walker::new(over.at, over.facing.opposite()));
+                assert_eq!(back, Walker::new(walker.at, walker.facing.opposite()));
+                // straight on around the cube
+                let around = (0..4 * size).fold(walker, |x, _| cube.wrap(x));
+                assert_eq!(around, walker);
+            }
+        }
+    }
+
+    #[test]
+    fn solution() {
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), 6032);
+        assert_eq!(Puzzle::part_two(Puzzle::parse(INPUT).unwrap()), Ok(5031));
+        // a board that is no net of a cube still wraps flat
+        let flat = Puzzle::parse("...#\n.#..\n\n3R2").unwrap();
+        assert_eq!(Puzzle::part_one(flat.clone()), 1013);
+        assert!(Puzzle::part_two(flat).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT
+            .replace("#...\n", "#..x\n")
+            .parse::<Notes>()
+            .unwrap_err();
+        assert_eq!(err.kind, BoardError::InvalidTile('x'));
+        assert_eq!(err.span, Span::new(3, 12, 1));
+        let err = INPUT.replace("R10L", "R10X").parse::<Notes>().unwrap_err();
+        assert_eq!(err.kind, BoardError::InvalidStep('X'));
+        assert_eq!(err.span, Span::new(14, 10, 1));
+        let err = INPUT.replace("\n\n", "\n").parse::<Notes>().unwrap_err();
+        assert_eq!(err.kind, BoardError::MissingPath);
+        let board = INPUT
+            .split_once("\n\n")
+            .unwrap()
+            .0
+            .replacen("...#\n", "...#....\n", 1);
+        let err = Cube::fold(&board.parse().unwrap()).unwrap_err();
+        assert_eq!(err, BoardError::UnevenFaces);
+        let err = Cube::fold(&"......".parse().unwrap()).unwrap_err();
+        assert_eq!(err, BoardError::NotANet);
+    }
+}
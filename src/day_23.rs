@@ -0,0 +1,237 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    str::FromStr,
+};
+
+use crate::{
+    automaton::{Automaton, Rule},
+    diagnostics::Grammar,
+    geometry::{Direction, Point},
+    grid::{Grid, GridError},
+    parse::ParseError,
+    solution::Solution,
+};
+
+/// directions an elf considers moving toward, first in the first round, each with the 3 cells it looks at
+const PROPOSALS: [[Direction; 3]; 4] = [
+    [Direction::Up, Direction::UpLeft, Direction::UpRight],
+    [Direction::Down, Direction::DownLeft, Direction::DownRight],
+    [Direction::Left, Direction::UpLeft, Direction::DownLeft],
+    [Direction::Right, Direction::UpRight, Direction::DownRight],
+];
+
+/// elves spreading out, each moving where it proposed unless another elf proposed the same tile
+#[derive(Debug, Clone, Copy)]
+pub struct Diffusion;
+
+impl Diffusion {
+    /// tile `elf` proposes to move to, the first direction considered moving on to the end each round
+    fn propose(elves: &HashSet<Point>, elf: Point, round: usize) -> Point {
+        let free =
+            |directions: &[Direction]| directions.iter().all(|x| !elves.contains(&elf.step(*x)));
+        if free(&Direction::ALL) {
+            return elf;
+        }
+        (0..PROPOSALS.len())
+            .map(|x| PROPOSALS[(round + x) % PROPOSALS.len()])
+            .find(|x| free(x))
+            .map_or(elf, |x| elf.step(x[0]))
+    }
+}
+
+impl Rule for Diffusion {
+    fn next(&self, elves: &HashSet<Point>, generation: usize) -> HashSet<Point> {
+        let moves: Vec<(Point, Point)> = elves
+            .iter()
+            .map(|x| (*x, Self::propose(elves, *x, generation)))
+            .collect();
+        let mut proposed: HashMap<Point, usize> = HashMap::new();
+        for (_, to) in moves.iter() {
+            *proposed.entry(*to).or_default() += 1;
+        }
+        moves
+            .into_iter()
+            .map(|(from, to)| match proposed[&to] {
+                1 => to,
+                _ => from,
+            })
+            .collect()
+    }
+}
+
+/// elves planting the grove, where the scan shows them
+#[derive(Debug, Clone)]
+pub struct Grove(pub Vec<Point>);
+
+impl Grove {
+    pub fn diffusion(&self) -> Automaton<Diffusion> {
+        Automaton::new(self.0.iter().copied(), Diffusion)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroveError {
+    Empty,
+    InvalidTile(char),
+    RaggedRow { expected: usize, found: usize },
+}
+
+impl Display for GroveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "expected at least one row of tiles"),
+            Self::InvalidTile(c) => write!(f, "expected `.` or `#`, found `{c}`"),
+            Self::RaggedRow { expected, found } => {
+                write!(f, "expected {expected} tiles in the row, found {found}")
+            }
+        }
+    }
+}
+
+impl Grammar for GroveError {
+    fn expected(&self) -> &'static str {
+        "<.|#>..., with rows of the same width"
+    }
+}
+
+impl From<GridError<char>> for GroveError {
+    fn from(v: GridError<char>) -> Self {
+        match v {
+            GridError::Empty => Self::Empty,
+            GridError::RaggedRow { expected, found } => Self::RaggedRow { expected, found },
+            GridError::InvalidCell(c) => Self::InvalidTile(c),
+        }
+    }
+}
+
+impl FromStr for Grove {
+    type Err = ParseError<GroveError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse(v, |c| match c {
+            '.' => Ok(false),
+            '#' => Ok(true),
+            _ => Err(c),
+        })
+        .map_err(|e| e.map(GroveError::from))?;
+        Ok(Self(
+            grid.iter().filter(|(_, x)| **x).map(|(at, _)| at).collect(),
+        ))
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 23;
+    const TITLE: &'static str = "Unstable Diffusion";
+
+    type Parsed = Grove;
+    type Error = ParseError<GroveError>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(grove: Self::Parsed) -> Self::One {
+        let mut diffusion = grove.diffusion();
+        diffusion.run(10);
+        diffusion.empty()
+    }
+    fn part_two(grove: Self::Parsed) -> Self::Two {
+        grove.diffusion().settle()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{geometry::Point, parse::Span, solution::Solution};
+
+    use super::{Grove, GroveError, Puzzle};
+
+    const INPUT: &str = "....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#..";
+
+    const SMALL: &str = ".....
+..##.
+..#..
+.....
+..##.
+.....";
+
+    #[test]
+    fn parse() {
+        let grove = SMALL.parse::<Grove>().unwrap();
+        assert_eq!(
+            grove.0,
+            [(2, 1), (3, 1), (2, 2), (2, 4), (3, 4)].map(Point::from)
+        );
+        assert_eq!(INPUT.parse::<Grove>().unwrap().0.len(), 22);
+    }
+
+    #[test]
+    fn rounds() {
+        let mut diffusion = SMALL.parse::<Grove>().unwrap().diffusion();
+        diffusion.step();
+        assert_eq!(diffusion.render(), "##\n..\n#.\n.#\n#.");
+        diffusion.step();
+        assert_eq!(diffusion.render(), ".##.\n#...\n...#\n....\n.#..");
+        diffusion.step();
+        assert_eq!(
+            diffusion.render(),
+            "..#..\n....#\n#....\n....#\n.....\n..#.."
+        );
+        assert!(!diffusion.step());
+        let mut diffusion = INPUT.parse::<Grove>().unwrap().diffusion();
+        diffusion.run(10);
+        assert_eq!(
+            diffusion.render(),
+            "......#.....
+..........#.
+.#.#..#.....
+.....#......
+..#.....#..#
+#......##...
+....##......
+.#........#.
+...#.#..#...
+............
+...#..#..#.."
+        );
+    }
+
+    #[test]
+    fn solution() {
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), 110);
+        assert_eq!(Puzzle::part_two(Puzzle::parse(INPUT).unwrap()), 20);
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT
+            .replace("##.#.##", "##.o.##")
+            .parse::<Grove>()
+            .unwrap_err();
+        assert_eq!(err.kind, GroveError::InvalidTile('o'));
+        assert_eq!(err.span, Span::new(6, 4, 1));
+        let err = INPUT
+            .replace("#.###..", "#.###.")
+            .parse::<Grove>()
+            .unwrap_err();
+        assert_eq!(
+            err.kind,
+            GroveError::RaggedRow {
+                expected: 7,
+                found: 6
+            }
+        );
+        let err = "".parse::<Grove>().unwrap_err();
+        assert_eq!(err.kind, GroveError::Empty);
+    }
+}
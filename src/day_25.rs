@@ -1,5 +1,13 @@
+use std::{fmt::Display, str::FromStr};
+
 use num_traits::pow;
 
+use crate::{
+    diagnostics::Grammar,
+    parse::{self, ParseError, Span},
+    solution::Solution,
+};
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SnafuDigit {
     Two,
@@ -31,7 +39,7 @@ impl SnafuNumber {
         }
         pow(5, self.0.len() - (at + 1))
     }
-    fn sum(&self) -> isize {
+    pub fn sum(&self) -> isize {
         self.0.iter().enumerate().fold(0isize, |acc, (idx, digit)| {
             acc + (self.get_place(idx) as isize * digit.as_digit())
         })
@@ -88,15 +96,38 @@ impl From<isize> for SnafuNumber {
     }
 }
 
-impl From<char> for SnafuDigit {
-    fn from(value: char) -> Self {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnafuError {
+    InvalidDigit(char),
+}
+
+impl Display for SnafuError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidDigit(c) => write!(f, "expected one of 2, 1, 0, -, =, found `{c}`"),
+        }
+    }
+}
+
+impl Grammar for SnafuError {
+    fn expected(&self) -> &'static str {
+        "<2|1|0|-|=>..."
+    }
+    fn hint(&self) -> Option<String> {
+        Some("`-` stands for -1 and `=` for -2".into())
+    }
+}
+
+impl TryFrom<char> for SnafuDigit {
+    type Error = SnafuError;
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '2' => Self::Two,
-            '1' => Self::One,
-            '0' => Self::Zero,
-            '-' => Self::Minus,
-            '=' => Self::DoubleMinus,
-            _ => panic!("should not happen"),
+            '2' => Ok(Self::Two),
+            '1' => Ok(Self::One),
+            '0' => Ok(Self::Zero),
+            '-' => Ok(Self::Minus),
+            '=' => Ok(Self::DoubleMinus),
+            _ => Err(SnafuError::InvalidDigit(value)),
         }
     }
 }
@@ -113,33 +144,58 @@ impl From<SnafuDigit> for char {
     }
 }
 
-impl ToString for SnafuNumber {
-    fn to_string(&self) -> String {
+impl Display for SnafuNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut acc = String::from("");
         for snafu in self.0.iter() {
             acc.push(char::from(*snafu));
         }
-        acc
+        write!(f, "{acc}")
     }
 }
 
-impl From<&str> for SnafuNumber {
-    fn from(value: &str) -> Self {
+impl FromStr for SnafuNumber {
+    type Err = ParseError<SnafuError>;
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
         let mut digits = vec![];
-        for character in value.chars() {
-            digits.push(character.into());
+        for (idx, character) in value.chars().enumerate() {
+            digits.push(
+                SnafuDigit::try_from(character)
+                    .map_err(|e| ParseError::new(e, Span::new(1, idx + 1, 1)))?,
+            );
         }
-        Self(digits)
+        Ok(Self(digits))
     }
 }
 
-impl From<&str> for SnafuNumbers {
-    fn from(value: &str) -> Self {
-        let mut snafus = vec![];
-        for line in value.lines() {
-            snafus.push(SnafuNumber::from(line));
-        }
-        Self(snafus)
+impl FromStr for SnafuNumbers {
+    type Err = ParseError<SnafuError>;
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(Self(parse::lines(value)?))
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 25;
+    const TITLE: &'static str = "Full of Hot Air";
+    const PARTS: u8 = 1;
+
+    type Parsed = SnafuNumbers;
+    type Error = ParseError<SnafuError>;
+    type One = String;
+    type Two = &'static str;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(snafus: Self::Parsed) -> Self::One {
+        SnafuNumber::from(snafus.sum()).to_string()
+    }
+    // there's no puzzle for the 50th star
+    fn part_two(_: Self::Parsed) -> Self::Two {
+        "Merry Christmas!"
     }
 }
 
@@ -147,12 +203,14 @@ impl From<&str> for SnafuNumbers {
 mod tests {
     use test_case::test_case;
 
-    use super::{SnafuDigit, SnafuNumber, SnafuNumbers};
+    use crate::parse::Span;
+
+    use super::{SnafuDigit, SnafuError, SnafuNumber, SnafuNumbers};
 
     #[test]
     fn simple() {
         let input = "2=-01";
-        let snafu = SnafuNumber::from(input);
+        let snafu = input.parse::<SnafuNumber>().unwrap();
         assert_eq!(
             snafu,
             SnafuNumber(vec![
@@ -181,7 +239,7 @@ mod tests {
     #[test_case("1=", 3 ; "1=")]
     #[test_case("122", 37 ; "122")]
     fn multiple(given: &str, expected: isize) {
-        let snafu = SnafuNumber::from(given);
+        let snafu = given.parse::<SnafuNumber>().unwrap();
         assert_eq!(snafu.sum(), expected);
     }
 
@@ -200,10 +258,17 @@ mod tests {
 12
 1=
 122";
-        let snafus = SnafuNumbers::from(input);
+        let snafus = input.parse::<SnafuNumbers>().unwrap();
         assert_eq!(snafus.0.len(), 13);
         assert_eq!(snafus.sum(), 4890);
         // all credits to Chris Biscardi
         assert_eq!(SnafuNumber::from(4890).to_string(), "2=-1=0");
     }
+
+    #[test]
+    fn invalid() {
+        let err = "1=-0-2\n12131".parse::<SnafuNumbers>().unwrap_err();
+        assert_eq!(err.kind, SnafuError::InvalidDigit('3'));
+        assert_eq!(err.span, Span::new(2, 4, 1));
+    }
 }
@@ -1,3 +1,11 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    diagnostics::Grammar,
+    parse::{self, ParseError, Span},
+    solution::Solution,
+};
+
 pub trait Priority {
     fn priority(&self) -> usize;
 }
@@ -93,20 +101,72 @@ impl CommonChar for Group {
 #[derive(Debug)]
 pub struct Rucksacks(Vec<Rucksack>);
 
-impl From<&str> for Rucksack {
-    fn from(input: &str) -> Self {
-        let input = input.trim();
-        let (first, second) = input.split_at(input.len() / 2);
-        Rucksack {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RucksackError {
+    InvalidItem(char),
+    OddItems(usize),
+    IncompleteGroup(usize),
+}
+
+impl Display for RucksackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidItem(v) => write!(f, "items should be letters, found `{v}`"),
+            Self::OddItems(v) => write!(f, "compartments should be of equal size, found {v} items"),
+            Self::IncompleteGroup(v) => write!(f, "groups should contain 3 rucksacks, found {v}"),
+        }
+    }
+}
+
+impl Grammar for RucksackError {
+    fn expected(&self) -> &'static str {
+        match self {
+            Self::IncompleteGroup(_) => "<items>, with rucksacks grouped by 3",
+            _ => "<items>, as letters from a to z and A to Z",
+        }
+    }
+    fn hint(&self) -> Option<String> {
+        match self {
+            Self::OddItems(_) => Some("both compartments hold as many items".into()),
+            _ => None,
+        }
+    }
+}
+
+/// trimmed items of a rucksack, ensured to have a priority
+fn items(line: &str) -> Result<&str, ParseError<RucksackError>> {
+    let items = line.trim();
+    if let Some((idx, item)) = items.char_indices().find(|(_, x)| !x.is_ascii_alphabetic()) {
+        return Err(ParseError::new(
+            RucksackError::InvalidItem(item),
+            Span::of(line, &items[idx..idx + item.len_utf8()]),
+        ));
+    }
+    Ok(items)
+}
+
+impl FromStr for Rucksack {
+    type Err = ParseError<RucksackError>;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let items = items(input)?;
+        if items.len() % 2 != 0 {
+            return Err(ParseError::new(
+                RucksackError::OddItems(items.len()),
+                Span::of(input, items),
+            ));
+        }
+        let (first, second) = items.split_at(items.len() / 2);
+        Ok(Rucksack {
             first: Compartment(first.into()),
             second: Compartment(second.into()),
-        }
+        })
     }
 }
 
-impl From<&str> for Rucksacks {
-    fn from(input: &str) -> Self {
-        Rucksacks(input.lines().map(Rucksack::from).collect())
+impl FromStr for Rucksacks {
+    type Err = ParseError<RucksackError>;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Ok(Rucksacks(parse::lines(input)?))
     }
 }
 
@@ -141,21 +201,33 @@ pub struct Group {
 #[derive(Debug)]
 pub struct Groups(Vec<Group>);
 
-impl From<&str> for Groups {
-    fn from(input: &str) -> Self {
-        let mut lines = input.lines();
+impl FromStr for Groups {
+    type Err = ParseError<RucksackError>;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut lines = vec![];
+        for (idx, line) in input.lines().enumerate() {
+            if !line.trim().is_empty() {
+                lines.push((idx, items(line).map_err(|e| e.shift(idx))?));
+            }
+        }
         let mut groups = vec![];
-        while let (Some(first), Some(second), Some(third)) =
-            (lines.next(), lines.next(), lines.next())
-        {
-            let group = Group {
-                first: first.trim().into(),
-                second: second.trim().into(),
-                third: third.trim().into(),
-            };
-            groups.push(group);
+        for chunk in lines.chunks(3) {
+            if let [(_, first), (_, second), (_, third)] = chunk {
+                groups.push(Group {
+                    first: first.to_string(),
+                    second: second.to_string(),
+                    third: third.to_string(),
+                });
+            } else {
+                let (idx, last) = chunk.last().expect("non-empty chunk");
+                return Err(ParseError::new(
+                    RucksackError::IncompleteGroup(chunk.len()),
+                    Span::of(last, last),
+                )
+                .shift(*idx));
+            }
         }
-        Groups(groups)
+        Ok(Groups(groups))
     }
 }
 
@@ -165,16 +237,41 @@ impl Priorities for Groups {
     }
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+
+    type Parsed = (Rucksacks, Groups);
+    type Error = ParseError<RucksackError>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        Ok((input.parse()?, input.parse()?))
+    }
+    fn part_one((rucksacks, _): Self::Parsed) -> Self::One {
+        rucksacks.priorities().iter().map(|(_, x)| x).sum()
+    }
+    fn part_two((_, groups): Self::Parsed) -> Self::Two {
+        groups.priorities().iter().map(|(_, x)| x).sum()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::parse::Span;
+
     use super::{
-        CommonChar, Compartment, Groups, Priorities, Priority, Rucksack, Rucksacks, UniqueChars,
+        CommonChar, Compartment, Groups, Priorities, Priority, Rucksack, RucksackError, Rucksacks,
+        UniqueChars,
     };
 
     #[test]
     fn convert_one() {
         let s = "vJrwpWtwJgWrhcsFMMfFFhFp";
-        let rucksack = Rucksack::from(s);
+        let rucksack: Rucksack = s.parse().unwrap();
         assert_eq!(rucksack.first.0.as_str(), "vJrwpWtwJgWr");
         assert_eq!(rucksack.second.0.as_str(), "hcsFMMfFFhFp");
     }
@@ -187,11 +284,14 @@ mod tests {
       wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
       ttgJtRGJQctTZtZT
       CrZsJsPPZsGzwwsLwLmpwMDw";
-        let rucksacks = Rucksacks::from(s);
+        let rucksacks: Rucksacks = s.parse().unwrap();
         assert_eq!(rucksacks.0.len(), 6);
-        assert_eq!(rucksacks.0.get(0).unwrap().first.0.as_str(), "vJrwpWtwJgWr");
         assert_eq!(
-            rucksacks.0.get(0).unwrap().second.0.as_str(),
+            rucksacks.0.first().unwrap().first.0.as_str(),
+            "vJrwpWtwJgWr"
+        );
+        assert_eq!(
+            rucksacks.0.first().unwrap().second.0.as_str(),
             "hcsFMMfFFhFp"
         );
     }
@@ -208,11 +308,11 @@ mod tests {
     #[test]
     fn find_common() {
         let s = "vJrwpWtwJgWrhcsFMMfFFhFp";
-        let rucksack = Rucksack::from(s);
+        let rucksack: Rucksack = s.parse().unwrap();
         assert_eq!(rucksack.common_char(), 'p');
 
         let s = "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL";
-        let rucksack = Rucksack::from(s);
+        let rucksack: Rucksack = s.parse().unwrap();
         assert_eq!(rucksack.common_char(), 'L');
     }
 
@@ -239,7 +339,7 @@ mod tests {
       wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
       ttgJtRGJQctTZtZT
       CrZsJsPPZsGzwwsLwLmpwMDw";
-        let rucksacks = Rucksacks::from(s);
+        let rucksacks: Rucksacks = s.parse().unwrap();
         let priorities = rucksacks.priorities();
         assert_eq!(
             priorities,
@@ -263,9 +363,21 @@ mod tests {
       wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
       ttgJtRGJQctTZtZT
       CrZsJsPPZsGzwwsLwLmpwMDw";
-        let groups = Groups::from(s);
+        let groups: Groups = s.parse().unwrap();
         assert_eq!(groups.0.len(), 2);
-        assert_eq!(groups.0.get(0).unwrap().common_char(), 'r');
+        assert_eq!(groups.0.first().unwrap().common_char(), 'r');
         assert_eq!(groups.0.get(1).unwrap().common_char(), 'Z');
     }
+
+    #[test]
+    fn invalid() {
+        let err = "vJrwpWtwJgWr\nab1d".parse::<Rucksacks>().unwrap_err();
+        assert_eq!(err.kind, RucksackError::InvalidItem('1'));
+        assert_eq!(err.span, Span::new(2, 3, 1));
+        let err = "abc".parse::<Rucksack>().unwrap_err();
+        assert_eq!(err.kind, RucksackError::OddItems(3));
+        let err = "ab\ncd\nef\ngh".parse::<Groups>().unwrap_err();
+        assert_eq!(err.kind, RucksackError::IncompleteGroup(1));
+        assert_eq!(err.span.line, 4);
+    }
 }
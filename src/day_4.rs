@@ -1,7 +1,14 @@
-use std::ops::RangeInclusive;
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    diagnostics::Grammar,
+    interval::Interval,
+    parse::{self, ParseError, Span},
+    solution::Solution,
+};
 
 #[derive(Debug, Clone)]
-pub struct Section(RangeInclusive<usize>);
+pub struct Section(Interval<usize>);
 
 #[derive(Debug, Clone)]
 pub struct Pair {
@@ -9,42 +16,95 @@ pub struct Pair {
     second: Section,
 }
 
+#[derive(Debug)]
 pub struct Pairs(pub Vec<Pair>);
 
-impl From<&str> for Section {
-    fn from(v: &str) -> Self {
-        let parts: Vec<usize> = v
-            .split('-')
-            .map(|x| x.parse::<usize>().expect("should be a digit"))
-            .collect();
-        if parts.len() > 2 {
-            panic!("there should be only 2 digits");
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssignmentError {
+    InvalidSection(String),
+    MissingSection,
+    TooManySections(String),
+    ReversedSection(String),
+}
+
+impl Display for AssignmentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidSection(v) => write!(f, "expected a section ID, found `{v}`"),
+            Self::MissingSection => write!(f, "missing section"),
+            Self::TooManySections(v) => write!(f, "there should be only 2 sections, found `{v}`"),
+            Self::ReversedSection(v) => {
+                write!(f, "expected a section starting before it ends, found `{v}`")
+            }
         }
-        let from = parts.get(0).expect("from should not be empty");
-        let to = parts.get(1).expect("to should not be empty");
-        Section(*from..=*to)
     }
 }
 
-impl From<&str> for Pair {
-    fn from(v: &str) -> Self {
-        let parts: Vec<&str> = v.trim().split(',').collect();
-        if parts.len() > 2 {
-            panic!("there should be only 2 digits");
+impl Grammar for AssignmentError {
+    fn expected(&self) -> &'static str {
+        "<n>-<n>,<n>-<n>"
+    }
+}
+
+impl FromStr for Section {
+    type Err = ParseError<AssignmentError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let mut parts = v.split('-');
+        let mut bound = || {
+            let part = parts.next().unwrap_or_default();
+            part.parse::<usize>().map_err(|_| {
+                let kind = AssignmentError::InvalidSection(part.to_string());
+                match part.is_empty() {
+                    true => ParseError::new(kind, Span::after(v)),
+                    false => ParseError::new(kind, Span::of(v, part)),
+                }
+            })
+        };
+        let from = bound()?;
+        let to = bound()?;
+        if let Some(part) = parts.next() {
+            return Err(ParseError::new(
+                AssignmentError::TooManySections(part.to_string()),
+                Span::of(v, part),
+            ));
+        }
+        match Interval::new(from, to) {
+            Some(interval) => Ok(Section(interval)),
+            None => Err(ParseError::new(
+                AssignmentError::ReversedSection(v.to_string()),
+                Span::of(v, v),
+            )),
         }
-        let first = Section::from(*parts.get(0).expect("first should not be empty"));
-        let second = Section::from(*parts.get(1).expect("second should not be empty"));
-        Pair { first, second }
     }
 }
 
-impl From<&str> for Pairs {
-    fn from(v: &str) -> Self {
-        let mut pairs = vec![];
-        for line in v.lines() {
-            pairs.push(Pair::from(line));
+impl FromStr for Pair {
+    type Err = ParseError<AssignmentError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let mut parts = v.trim().split(',');
+        let mut section = || match parts.next() {
+            Some(part) => part.parse::<Section>().map_err(|e| e.within(v, part)),
+            None => Err(ParseError::new(
+                AssignmentError::MissingSection,
+                Span::after(v),
+            )),
+        };
+        let first = section()?;
+        let second = section()?;
+        if let Some(part) = parts.next() {
+            return Err(ParseError::new(
+                AssignmentError::TooManySections(part.to_string()),
+                Span::of(v, part),
+            ));
         }
-        Pairs(pairs)
+        Ok(Pair { first, second })
+    }
+}
+
+impl FromStr for Pairs {
+    type Err = ParseError<AssignmentError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        Ok(Pairs(parse::lines(v)?))
     }
 }
 
@@ -58,35 +118,25 @@ pub trait AnyOverlap {
     fn overlap_one_in_any_way(&self) -> bool;
 }
 
-impl Overlap for RangeInclusive<usize> {
+impl Overlap for Interval<usize> {
     fn overlap_all(&self, other: Self) -> bool {
-        for v in other {
-            if !self.contains(&v) {
-                return false;
-            }
-        }
-        true
+        self.covers(&other)
     }
     fn overlap_one(&self, other: Self) -> bool {
-        for v in other {
-            if self.contains(&v) {
-                return true;
-            }
-        }
-        false
+        self.overlaps(&other)
     }
 }
 
 impl AnyOverlap for Pair {
     fn overlap_all_in_any_way(&self) -> bool {
-        let outer = self.first.0.clone();
-        let inner = self.second.0.clone();
-        outer.overlap_all(inner.clone()) || inner.overlap_all(outer)
+        let outer = self.first.0;
+        let inner = self.second.0;
+        outer.overlap_all(inner) || inner.overlap_all(outer)
     }
     fn overlap_one_in_any_way(&self) -> bool {
-        let outer = self.first.0.clone();
-        let inner = self.second.0.clone();
-        outer.overlap_one(inner.clone()) || inner.overlap_one(outer)
+        let outer = self.first.0;
+        let inner = self.second.0;
+        outer.overlap_one(inner) || inner.overlap_one(outer)
     }
 }
 
@@ -112,9 +162,33 @@ impl Pairs {
     }
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Camp Cleanup";
+
+    type Parsed = Pairs;
+    type Error = ParseError<AssignmentError>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(pairs: Self::Parsed) -> Self::One {
+        pairs.overlap_range().0.len()
+    }
+    fn part_two(pairs: Self::Parsed) -> Self::Two {
+        pairs.overlap_digit().0.len()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Pairs;
+    use crate::parse::Span;
+
+    use super::{AssignmentError, Pairs};
 
     #[test]
     fn convert() {
@@ -124,13 +198,13 @@ mod tests {
 2-8,3-7
 6-6,4-6
 2-6,4-8";
-        let pairs = Pairs::from(s);
-        let one = pairs.0.get(0).unwrap();
-        assert_eq!(one.first.0, 2..=4);
-        assert_eq!(one.second.0, 6..=8);
+        let pairs: Pairs = s.parse().unwrap();
+        let one = pairs.0.first().unwrap();
+        assert_eq!(one.first.0, (2..=4).into());
+        assert_eq!(one.second.0, (6..=8).into());
         let two = pairs.0.get(1).unwrap();
-        assert_eq!(two.first.0, 2..=3);
-        assert_eq!(two.second.0, 4..=5);
+        assert_eq!(two.first.0, (2..=3).into());
+        assert_eq!(two.second.0, (4..=5).into());
     }
 
     #[test]
@@ -141,7 +215,7 @@ mod tests {
 2-8,3-7
 6-6,4-6
 2-6,4-8";
-        let pairs = Pairs::from(s);
+        let pairs: Pairs = s.parse().unwrap();
         let pairs = pairs.overlap_range();
         assert_eq!(pairs.0.len(), 2);
     }
@@ -154,8 +228,24 @@ mod tests {
 2-8,3-7
 6-6,4-6
 2-6,4-8";
-        let pairs = Pairs::from(s);
+        let pairs: Pairs = s.parse().unwrap();
         let pairs = pairs.overlap_digit();
         assert_eq!(pairs.0.len(), 4);
     }
+
+    #[test]
+    fn invalid() {
+        let err = "2-4,6-8\n2-3,4-x".parse::<Pairs>().unwrap_err();
+        assert_eq!(err.kind, AssignmentError::InvalidSection("x".into()));
+        assert_eq!(err.span, Span::new(2, 7, 1));
+        let err = "2-4".parse::<Pairs>().unwrap_err();
+        assert_eq!(err.kind, AssignmentError::MissingSection);
+        let err = "2-4,6-8,1-1".parse::<Pairs>().unwrap_err();
+        assert_eq!(err.kind, AssignmentError::TooManySections("1-1".into()));
+        let err = "2-4,6-".parse::<Pairs>().unwrap_err();
+        assert_eq!(err.span, Span::new(1, 7, 1));
+        let err = "2-4,8-6".parse::<Pairs>().unwrap_err();
+        assert_eq!(err.kind, AssignmentError::ReversedSection("8-6".into()));
+        assert_eq!(err.span, Span::new(1, 5, 3));
+    }
 }
@@ -1,7 +1,13 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, fmt::Display, str::FromStr};
 
 use regex::Regex;
 
+use crate::{
+    diagnostics::Grammar,
+    parse::{ParseError, Span},
+    solution::Solution,
+};
+
 #[allow(dead_code)]
 #[derive(PartialEq)]
 pub enum CrateMover {
@@ -20,112 +26,166 @@ pub struct Move {
     steps: usize,
     from: usize,
     to: usize,
+    /// where `from` and `to` are located in the input
+    spans: (Span, Span),
 }
 
 #[derive(Debug)]
 pub struct Moves(Vec<Move>);
 
-impl From<&str> for Moves {
-    fn from(v: &str) -> Self {
-        let mut moves = vec![];
-        let re = Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
-        for cap in re.captures_iter(v) {
-            moves.push(Move {
-                steps: cap[1].parse().expect("valid digit for steps"),
-                from: cap[2].parse().expect("valid digit for from"),
-                to: cap[3].parse().expect("valid digit for to"),
-            });
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CargoError {
+    MalformedCrate(String),
+    MalformedMove(String),
+    InvalidNumber(String),
+    UnknownStack(usize),
+}
+
+impl Display for CargoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MalformedCrate(v) => write!(f, "expected a crate like `[A]`, found `{v}`"),
+            Self::MalformedMove(v) => write!(f, "malformed move `{v}`"),
+            Self::InvalidNumber(v) => write!(f, "expected a number, found `{v}`"),
+            Self::UnknownStack(v) => write!(f, "there is no stack {v}"),
         }
-        Moves(moves)
     }
 }
 
-#[derive(Debug)]
-pub struct Stacks(Vec<Stack>);
+impl Grammar for CargoError {
+    fn expected(&self) -> &'static str {
+        match self {
+            Self::MalformedCrate(_) => "[<X>] [<X>] ..., with 4 characters per stack",
+            _ => "move <n> from <n> to <n>",
+        }
+    }
+    fn hint(&self) -> Option<String> {
+        match self {
+            Self::UnknownStack(_) => {
+                Some("stacks are numbered from 1, as labelled under the drawing".into())
+            }
+            _ => None,
+        }
+    }
+}
 
-impl From<&str> for Crate {
-    fn from(v: &str) -> Self {
-        let mut chars = v.chars();
-        chars.next();
-        Self(chars.next().expect("a char"))
+/// first token of `line` which doesn't fit `move <n> from <n> to <n>`
+fn misfit(line: &str) -> Span {
+    let mut tokens = line.split_whitespace();
+    for expected in ["move", "<n>", "from", "<n>", "to", "<n>"] {
+        match tokens.next() {
+            None => return Span::after(line.trim_end()),
+            Some(token) if token == expected => {}
+            Some(token) if expected == "<n>" && token.chars().all(|x| x.is_ascii_digit()) => {}
+            Some(token) => return Span::of(line, token),
+        }
     }
+    tokens
+        .next()
+        .map(|x| Span::of(line, x))
+        .unwrap_or_else(|| Span::of(line, line.trim()))
 }
 
-impl From<&Vec<Option<&str>>> for Stack {
-    fn from(v: &Vec<Option<&str>>) -> Self {
-        let mut stack = VecDeque::new();
-        for line in v {
-            if line.is_some() {
-                stack.push_back(Crate::from(line.unwrap()));
+// moves are listed after the drawing of the stacks, if any
+impl FromStr for Moves {
+    type Err = ParseError<CargoError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let mut moves = vec![];
+        let re = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+        let start = v
+            .lines()
+            .position(|x| x.trim().is_empty())
+            .map(|x| x + 1)
+            .unwrap_or(0);
+        for (idx, line) in v.lines().enumerate().skip(start) {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
             }
+            let cap = re.captures(trimmed).ok_or_else(|| {
+                ParseError::new(CargoError::MalformedMove(trimmed.to_string()), misfit(line))
+                    .shift(idx)
+            })?;
+            let number = |at: usize| {
+                let digits = cap.get(at).expect("capture group").as_str();
+                let span = Span::of(line, digits);
+                digits.parse::<usize>().map(|x| (x, span)).map_err(|_| {
+                    ParseError::new(CargoError::InvalidNumber(digits.to_string()), span).shift(idx)
+                })
+            };
+            let (steps, _) = number(1)?;
+            let (from, from_at) = number(2)?;
+            let (to, to_at) = number(3)?;
+            moves.push(Move {
+                steps,
+                from,
+                to,
+                spans: (from_at.shift(idx), to_at.shift(idx)),
+            });
         }
-        Stack(stack)
+        Ok(Moves(moves))
     }
 }
 
-impl From<Vec<Vec<Option<&str>>>> for Stacks {
-    fn from(v: Vec<Vec<Option<&str>>>) -> Self {
-        let mut stacks = vec![];
-        for lines in v.iter() {
-            stacks.push(Stack::from(lines));
+#[derive(Debug)]
+pub struct Stacks(Vec<Stack>);
+
+impl FromStr for Crate {
+    type Err = ParseError<CargoError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let mut chars = v.chars();
+        match (chars.next(), chars.next(), chars.next(), chars.next()) {
+            (Some('['), Some(c), Some(']'), None) if c.is_alphanumeric() => Ok(Self(c)),
+            _ => Err(ParseError::new(
+                CargoError::MalformedCrate(v.to_string()),
+                Span::of(v, v),
+            )),
         }
-        Stacks(stacks)
     }
 }
 
 // could have been done simpler by jumping from index to index ...
-impl From<&str> for Stacks {
-    fn from(v: &str) -> Self {
-        let mut acc = vec![];
-        #[allow(unused_assignments)]
-        let mut till = 0;
-        let mut max = 0;
-        for line in v.lines() {
-            if !line.contains(']') {
-                break;
-            }
-            till = line.rfind(']').unwrap() + 1;
-            acc.push(&line[..till]);
-            if line.len() > max {
-                max = line.len();
-            }
-        }
-        let mut stack = vec![];
+impl FromStr for Stacks {
+    type Err = ParseError<CargoError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let drawing: Vec<&str> = v.lines().take_while(|x| x.contains(']')).collect();
+        let max = drawing.iter().map(|x| x.len()).max().unwrap_or(0);
         let mut stacks = vec![];
         let mut index = 0;
         while index < max {
-            for line in acc.iter() {
-                if line.len() >= index + 3 {
-                    let part = &line[index..index + 3];
-                    if !part.trim().is_empty() {
-                        stack.push(Some(part));
-                    }
+            let mut stack = VecDeque::new();
+            for (idx, line) in drawing.iter().enumerate() {
+                let part = line.get(index..index + 3).unwrap_or_default();
+                if !part.trim().is_empty() {
+                    stack
+                        .push_back(part.parse().map_err(|e: ParseError<CargoError>| {
+                            e.within(line, part).shift(idx)
+                        })?);
                 }
             }
-            stacks.push(stack.clone());
-            stack.clear();
+            stacks.push(Stack(stack));
             index += 4;
         }
-        Stacks::from(stacks)
+        Ok(Stacks(stacks))
     }
 }
 
 #[allow(dead_code)]
 impl Stack {
     fn get_top_crate(&self) -> Option<char> {
-        for item in self.0.iter() {
+        if let Some(item) = self.0.iter().next() {
             return Some(item.0);
         }
         None
     }
     fn get_top_crate_idx(&self) -> Option<usize> {
-        if self.0.len() == 0 {
+        if self.0.is_empty() {
             return None;
         }
         Some(0)
     }
     fn get_bottom_crate_idx(&self) -> Option<usize> {
-        if self.0.len() == 0 {
+        if self.0.is_empty() {
             return None;
         }
         Some(self.0.len() - 1)
@@ -137,7 +197,7 @@ impl Stacks {
     pub fn bulk_take_crates(&mut self, from: usize, num: usize) -> Vec<Crate> {
         let stack = self.0.get_mut(from).unwrap();
         let mut crates = vec![];
-        let mut num = num.clone();
+        let mut num = num;
         while num > 0 {
             crates.push(stack.0.pop_front().unwrap());
             num -= 1;
@@ -173,13 +233,50 @@ impl Stacks {
     }
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Supply Stacks";
+
+    type Parsed = (Stacks, Moves);
+    type Error = ParseError<CargoError>;
+    type One = String;
+    type Two = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        let stacks: Stacks = input.parse()?;
+        let moves: Moves = input.parse()?;
+        for m in moves.0.iter() {
+            for (stack, span) in [(m.from, m.spans.0), (m.to, m.spans.1)] {
+                if stack == 0 || stack > stacks.0.len() {
+                    return Err(ParseError::new(CargoError::UnknownStack(stack), span));
+                }
+            }
+        }
+        Ok((stacks, moves))
+    }
+    fn part_one((mut stacks, moves): Self::Parsed) -> Self::One {
+        stacks.multiple_move_crates(&moves, &CrateMover::NineThousands);
+        stacks.get_top_crates()
+    }
+    fn part_two((mut stacks, moves): Self::Parsed) -> Self::Two {
+        stacks.multiple_move_crates(&moves, &CrateMover::NineThousandsOne);
+        stacks.get_top_crates()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::day_5::{CrateMover, Moves, Stack};
+    use crate::{
+        day_5::{CargoError, CrateMover, Moves, Puzzle, Stack},
+        parse::Span,
+        solution::Solution,
+    };
 
     use super::{Crate, Stacks};
 
-    const INPUT: &'static str = "    [D]    
+    const INPUT: &str = "    [D]    
 [N] [C]    
 [Z] [M] [P]
   1   2   3 
@@ -191,11 +288,11 @@ move 1 from 1 to 2";
 
     #[test]
     fn convert() {
-        let stacks = Stacks::from(INPUT);
+        let stacks: Stacks = INPUT.parse().unwrap();
         assert_eq!(stacks.0.len(), 3);
-        let moves = Moves::from(INPUT);
+        let moves: Moves = INPUT.parse().unwrap();
         assert_eq!(moves.0.len(), 4);
-        let current = moves.0.get(0).unwrap();
+        let current = moves.0.first().unwrap();
         assert_eq!(current.steps, 1);
         assert_eq!(current.from, 2);
         assert_eq!(current.to, 1);
@@ -215,12 +312,12 @@ move 1 from 1 to 2";
 
     #[test]
     fn steps_9000() {
-        let mut stacks = Stacks::from(INPUT);
-        let moves = Moves::from(INPUT);
+        let mut stacks: Stacks = INPUT.parse().unwrap();
+        let moves: Moves = INPUT.parse().unwrap();
 
-        let first = moves.0.get(0).unwrap();
+        let first = moves.0.first().unwrap();
         stacks.move_crates(first, &CrateMover::NineThousands);
-        let current = stacks.0.get(0).unwrap();
+        let current = stacks.0.first().unwrap();
         assert_eq!(*current, Stack([Crate('D'), Crate('N'), Crate('Z')].into()));
         let current = stacks.0.get(1).unwrap();
         assert_eq!(*current, Stack([Crate('C'), Crate('M')].into()));
@@ -229,7 +326,7 @@ move 1 from 1 to 2";
 
         let second = moves.0.get(1).unwrap();
         stacks.move_crates(second, &CrateMover::NineThousands);
-        let current = stacks.0.get(0).unwrap();
+        let current = stacks.0.first().unwrap();
         assert_eq!(*current, Stack([].into()));
         let current = stacks.0.get(1).unwrap();
         assert_eq!(*current, Stack([Crate('C'), Crate('M')].into()));
@@ -241,7 +338,7 @@ move 1 from 1 to 2";
 
         let third = moves.0.get(2).unwrap();
         stacks.move_crates(third, &CrateMover::NineThousands);
-        let current = stacks.0.get(0).unwrap();
+        let current = stacks.0.first().unwrap();
         assert_eq!(*current, Stack([Crate('M'), Crate('C')].into()));
         let current = stacks.0.get(1).unwrap();
         assert_eq!(*current, Stack([].into()));
@@ -253,7 +350,7 @@ move 1 from 1 to 2";
 
         let fourth = moves.0.get(3).unwrap();
         stacks.move_crates(fourth, &CrateMover::NineThousands);
-        let current = stacks.0.get(0).unwrap();
+        let current = stacks.0.first().unwrap();
         assert_eq!(*current, Stack([Crate('C')].into()));
         let current = stacks.0.get(1).unwrap();
         assert_eq!(*current, Stack([Crate('M')].into()));
@@ -266,12 +363,12 @@ move 1 from 1 to 2";
 
     #[test]
     fn steps_9001() {
-        let mut stacks = Stacks::from(INPUT);
-        let moves = Moves::from(INPUT);
+        let mut stacks: Stacks = INPUT.parse().unwrap();
+        let moves: Moves = INPUT.parse().unwrap();
 
-        let first = moves.0.get(0).unwrap();
+        let first = moves.0.first().unwrap();
         stacks.move_crates(first, &CrateMover::NineThousandsOne);
-        let current = stacks.0.get(0).unwrap();
+        let current = stacks.0.first().unwrap();
         assert_eq!(*current, Stack([Crate('D'), Crate('N'), Crate('Z')].into()));
         let current = stacks.0.get(1).unwrap();
         assert_eq!(*current, Stack([Crate('C'), Crate('M')].into()));
@@ -280,7 +377,7 @@ move 1 from 1 to 2";
 
         let second = moves.0.get(1).unwrap();
         stacks.move_crates(second, &CrateMover::NineThousandsOne);
-        let current = stacks.0.get(0).unwrap();
+        let current = stacks.0.first().unwrap();
         assert_eq!(*current, Stack([].into()));
         let current = stacks.0.get(1).unwrap();
         assert_eq!(*current, Stack([Crate('C'), Crate('M')].into()));
@@ -292,7 +389,7 @@ move 1 from 1 to 2";
 
         let third = moves.0.get(2).unwrap();
         stacks.move_crates(third, &CrateMover::NineThousandsOne);
-        let current = stacks.0.get(0).unwrap();
+        let current = stacks.0.first().unwrap();
         assert_eq!(*current, Stack([Crate('C'), Crate('M')].into()));
         let current = stacks.0.get(1).unwrap();
         assert_eq!(*current, Stack([].into()));
@@ -304,7 +401,7 @@ move 1 from 1 to 2";
 
         let fourth = moves.0.get(3).unwrap();
         stacks.move_crates(fourth, &CrateMover::NineThousandsOne);
-        let current = stacks.0.get(0).unwrap();
+        let current = stacks.0.first().unwrap();
         assert_eq!(*current, Stack([Crate('M')].into()));
         let current = stacks.0.get(1).unwrap();
         assert_eq!(*current, Stack([Crate('C')].into()));
@@ -317,17 +414,44 @@ move 1 from 1 to 2";
 
     #[test]
     fn swap_9000() {
-        let mut stacks = Stacks::from(INPUT);
-        let moves = Moves::from(INPUT);
+        let mut stacks: Stacks = INPUT.parse().unwrap();
+        let moves: Moves = INPUT.parse().unwrap();
         stacks.multiple_move_crates(&moves, &CrateMover::NineThousands);
         assert_eq!(stacks.get_top_crates().as_str(), "CMZ");
     }
 
     #[test]
     fn swap_9001() {
-        let mut stacks = Stacks::from(INPUT);
-        let moves = Moves::from(INPUT);
+        let mut stacks: Stacks = INPUT.parse().unwrap();
+        let moves: Moves = INPUT.parse().unwrap();
         stacks.multiple_move_crates(&moves, &CrateMover::NineThousandsOne);
         assert_eq!(stacks.get_top_crates().as_str(), "MCD");
     }
+
+    #[test]
+    fn solution() {
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), "CMZ");
+        assert_eq!(Puzzle::part_two(Puzzle::parse(INPUT).unwrap()), "MCD");
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT.replace("[M]", "[M}").parse::<Stacks>().unwrap_err();
+        assert_eq!(err.kind, CargoError::MalformedCrate("[M}".into()));
+        assert_eq!(err.span, Span::new(3, 5, 3));
+        let err = INPUT
+            .replace("move 3", "mvoe 3")
+            .parse::<Moves>()
+            .unwrap_err();
+        assert_eq!(
+            err.kind,
+            CargoError::MalformedMove("mvoe 3 from 1 to 3".into())
+        );
+        assert_eq!(err.span, Span::new(7, 1, 4));
+        let err = INPUT.replace("to 3", "to").parse::<Moves>().unwrap_err();
+        assert_eq!(err.span, Span::new(7, 17, 1));
+        let err = Puzzle::parse(&INPUT.replace("to 3", "to 4")).unwrap_err();
+        assert_eq!(err.kind, CargoError::UnknownStack(4));
+        assert_eq!(err.span, Span::new(7, 18, 1));
+    }
 }
@@ -1,10 +1,16 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt::Display};
+
+use crate::{
+    diagnostics::Grammar,
+    parse::{ParseError, Span},
+    solution::Solution,
+};
 
 pub trait IsStartMarker {
     fn is_start_marker(&self) -> bool;
 }
 
-impl<'a> IsStartMarker for &'a str {
+impl IsStartMarker for &str {
     fn is_start_marker(&self) -> bool {
         let mut set = HashSet::with_capacity(4);
         for c in self.chars() {
@@ -21,25 +27,91 @@ pub fn start_at(buf: &str, len: usize) -> usize {
     let bound = buf.len();
     let delim = len - 1;
     while index <= bound {
-        if index > delim {
-            if (&buf[index - len..index]).is_start_marker() {
-                return index;
-            }
+        if index > delim && (&buf[index - len..index]).is_start_marker() {
+            return index;
         }
         index += 1;
     }
     0
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DatastreamError {
+    UnexpectedCharacter(char),
+    ExtraLine,
+}
+
+impl Display for DatastreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedCharacter(v) => write!(f, "expected a lowercase letter, found `{v}`"),
+            Self::ExtraLine => write!(f, "datastream should fit on a single line"),
+        }
+    }
+}
+
+impl Grammar for DatastreamError {
+    fn expected(&self) -> &'static str {
+        "<a-z>... on a single line"
+    }
+}
+
+/// single line datastream, made of lowercase letters
+pub fn datastream(input: &str) -> Result<&str, ParseError<DatastreamError>> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let Some((idx, line)) = lines.next() else {
+        return Ok("");
+    };
+    if let Some((extra, line)) = lines.next() {
+        return Err(ParseError::new(DatastreamError::ExtraLine, Span::of(line, line)).shift(extra));
+    }
+    let buf = line.trim();
+    if let Some((at, c)) = buf.char_indices().find(|(_, x)| !x.is_ascii_lowercase()) {
+        return Err(ParseError::new(
+            DatastreamError::UnexpectedCharacter(c),
+            Span::of(line, &buf[at..at + c.len_utf8()]),
+        )
+        .shift(idx));
+    }
+    Ok(buf)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Tuning Trouble";
+
+    type Parsed = String;
+    type Error = ParseError<DatastreamError>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        datastream(input).map(str::to_string)
+    }
+    fn part_one(buf: Self::Parsed) -> Self::One {
+        start_at(buf.as_str(), 4)
+    }
+    fn part_two(buf: Self::Parsed) -> Self::Two {
+        start_at(buf.as_str(), 14)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::start_at;
+    use crate::parse::Span;
+
+    use super::{datastream, start_at, DatastreamError};
 
-    const ONE: &'static str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
-    const TWO: &'static str = "bvwbjplbgvbhsrlpgdmjqwftvncz";
-    const THREE: &'static str = "nppdvjthqldpwncqszvftbrmjlhg";
-    const FOUR: &'static str = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
-    const FIVE: &'static str = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
+    const ONE: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+    const TWO: &str = "bvwbjplbgvbhsrlpgdmjqwftvncz";
+    const THREE: &str = "nppdvjthqldpwncqszvftbrmjlhg";
+    const FOUR: &str = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
+    const FIVE: &str = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
 
     #[test]
     fn starter() {
@@ -60,4 +132,15 @@ mod tests {
         assert_eq!(start_at(FOUR, LEN), 29);
         assert_eq!(start_at(FIVE, LEN), 26);
     }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(datastream(&format!("\n{ONE}\n")), Ok(ONE));
+        let err = datastream("mjqjpq4gbl").unwrap_err();
+        assert_eq!(err.kind, DatastreamError::UnexpectedCharacter('4'));
+        assert_eq!(err.span, Span::new(1, 7, 1));
+        let err = datastream("mjqj\npqmg").unwrap_err();
+        assert_eq!(err.kind, DatastreamError::ExtraLine);
+        assert_eq!(err.span.line, 2);
+    }
 }
@@ -1,6 +1,10 @@
-#![allow(dead_code)]
+use std::{fmt::Display, str::FromStr};
 
-use std::fmt::Display;
+use crate::{
+    diagnostics::Grammar,
+    parse::{self, ParseError, Span},
+    solution::{Solution, Unsolvable},
+};
 
 #[derive(Debug, Clone)]
 pub enum Resource {
@@ -62,7 +66,10 @@ impl Default for Folder {
 }
 
 impl Folder {
-    fn path(&self) -> FullPath {
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+    pub fn path(&self) -> FullPath {
         match (&self.parent, &self.name) {
             (Some(ref parent), name) if parent.len() == 1 => {
                 FullPath(format!("{}{}", parent, name))
@@ -104,45 +111,68 @@ impl Default for FileSystem {
     }
 }
 
-impl From<&str> for StdOut {
-    fn from(v: &str) -> Self {
-        let mut fs = vec![];
-        for line in v.lines() {
-            fs.push(Command::from(line));
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerminalError {
+    UnknownCommand(String),
+    InvalidSize(String),
+    MalformedLine(String),
+}
+
+impl Display for TerminalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownCommand(v) => write!(f, "unknown command `{v}`"),
+            Self::InvalidSize(v) => write!(f, "expected a file size, found `{v}`"),
+            Self::MalformedLine(v) => write!(f, "expected a command or a listing, found `{v}`"),
         }
-        Self(fs)
     }
 }
 
-impl From<&str> for Command {
-    fn from(v: &str) -> Self {
-        if v.starts_with("$") {
-            let inner = &v["$".len() + 1..];
-            if inner.starts_with("ls") {
-                return Command::Ls;
-            }
-            if inner.starts_with("cd") {
-                if inner.ends_with('/') {
-                    return Command::Root;
-                }
-                if inner.ends_with("..") {
-                    return Command::Back;
-                }
-                return Command::To(inner["cd".len() + 1..].to_string());
-            }
+impl Grammar for TerminalError {
+    fn expected(&self) -> &'static str {
+        "$ cd <dir> | $ ls | dir <name> | <size> <name>"
+    }
+    fn hint(&self) -> Option<String> {
+        match self {
+            Self::UnknownCommand(_) => Some("only `cd` and `ls` were typed in the terminal".into()),
+            _ => None,
         }
-        if v.starts_with("dir") {
-            return Command::Dir(v["dir".len() + 1..].to_string());
+    }
+}
+
+impl FromStr for StdOut {
+    type Err = ParseError<TerminalError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        Ok(Self(parse::lines(v)?))
+    }
+}
+
+impl FromStr for Command {
+    type Err = ParseError<TerminalError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = v.split_whitespace().collect();
+        match parts.as_slice() {
+            ["$", "ls"] => Ok(Command::Ls),
+            ["$", "cd", "/"] => Ok(Command::Root),
+            ["$", "cd", ".."] => Ok(Command::Back),
+            ["$", "cd", dir] => Ok(Command::To(dir.to_string())),
+            ["$", command, ..] if !matches!(*command, "cd" | "ls") => Err(ParseError::new(
+                TerminalError::UnknownCommand(command.to_string()),
+                Span::of(v, command),
+            )),
+            ["dir", name] => Ok(Command::Dir(name.to_string())),
+            [size, name] if *size != "$" => match size.parse::<usize>() {
+                Ok(size) => Ok(Command::File(size, name.to_string())),
+                Err(_) => Err(ParseError::new(
+                    TerminalError::InvalidSize(size.to_string()),
+                    Span::of(v, size),
+                )),
+            },
+            _ => Err(ParseError::new(
+                TerminalError::MalformedLine(v.trim().to_string()),
+                Span::of(v, v.trim()),
+            )),
         }
-        let parts: Vec<&str> = v.split(' ').collect();
-        assert!(parts.len() == 2);
-        let size = parts
-            .first()
-            .expect("filesize")
-            .parse::<usize>()
-            .expect("should be a digit");
-        let name = parts.get(1).expect("filename");
-        return Command::File(size, name.to_string());
     }
 }
 
@@ -192,7 +222,13 @@ impl From<StdOut> for FileSystem {
 }
 
 impl File {
-    fn is_in(&self, path: &FullPath) -> bool {
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+    pub fn size(&self) -> usize {
+        self.size
+    }
+    pub fn is_in(&self, path: &FullPath) -> bool {
         &self.parent == path
     }
 }
@@ -285,14 +321,13 @@ impl FileSystem {
             .sum()
     }
     pub fn unused_space(&self, total: usize) -> usize {
-        total - self.find_nested_files(&"/".into()).size()
+        total.saturating_sub(self.find_nested_files(&"/".into()).size())
     }
+    /// space still missing to reach `required`, if any
     pub fn need_to_free(&self, total: usize, required: usize) -> Option<usize> {
-        let diff = required - self.unused_space(total);
-        if diff > 0 {
-            return Some(diff);
-        }
-        None
+        required
+            .checked_sub(self.unused_space(total))
+            .filter(|x| *x > 0)
     }
     pub fn smallest_of_the_biggest(
         &self,
@@ -306,19 +341,53 @@ impl FileSystem {
                 .map(|x| (self.dir_size(&x.path()), x))
                 .collect();
             dirs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
-            return Some(dirs.get(0).expect("dir for deletion").clone());
+            return dirs.first().copied();
         }
         None
     }
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "No Space Left On Device";
+
+    type Parsed = FileSystem;
+    type Error = ParseError<TerminalError>;
+    type One = usize;
+    type Two = Result<usize, Unsolvable>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        Ok(FileSystem::from(input.parse::<StdOut>()?))
+    }
+    fn part_one(fs: Self::Parsed) -> Self::One {
+        fs.sum_lightweight_dirs(100_000)
+    }
+    fn part_two(fs: Self::Parsed) -> Self::Two {
+        let (total, required) = (70_000_000, 30_000_000);
+        if fs.need_to_free(total, required).is_none() {
+            return Err(Unsolvable::new(
+                "enough space is already unused, no directory needs deleting",
+            ));
+        }
+        fs.smallest_of_the_biggest(total, required)
+            .map(|x| x.0)
+            .ok_or_else(|| Unsolvable::new("no directory frees enough space"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::day_7::{FileSystem, StdOut};
+    use crate::{
+        day_7::{FileSystem, Puzzle, StdOut},
+        parse::Span,
+        solution::{Solution, Unsolvable},
+    };
 
-    use super::Command;
+    use super::{Command, TerminalError};
 
-    const INPUT: &'static str = "$ cd /
+    const INPUT: &str = "$ cd /
 $ ls
 dir a
 14848514 b.txt
@@ -345,33 +414,33 @@ $ ls
     #[test]
     fn parse() {
         let line = "$ ls";
-        let cmd = Command::from(line);
+        let cmd = line.parse::<Command>().unwrap();
         assert_eq!(cmd, Command::Ls);
 
         let line = "$ cd /";
-        let cmd = Command::from(line);
+        let cmd = line.parse::<Command>().unwrap();
         assert_eq!(cmd, Command::Root);
 
         let line = "$ cd ..";
-        let cmd = Command::from(line);
+        let cmd = line.parse::<Command>().unwrap();
         assert_eq!(cmd, Command::Back);
 
         let line = "$ cd e";
-        let cmd = Command::from(line);
+        let cmd = line.parse::<Command>().unwrap();
         assert_eq!(cmd, Command::To("e".into()));
 
         let line = "dir a";
-        let folder = Command::from(line);
+        let folder = line.parse::<Command>().unwrap();
         assert_eq!(folder, Command::Dir("a".into()));
 
         let line = "8033020 d.log";
-        let file = Command::from(line);
+        let file = line.parse::<Command>().unwrap();
         assert_eq!(file, Command::File(8033020, "d.log".into()))
     }
 
     #[test]
     fn sizes() {
-        let stdout = StdOut::from(INPUT);
+        let stdout = INPUT.parse::<StdOut>().unwrap();
         let fs = FileSystem::from(stdout);
         let size = fs.dir_size(&"/a/e".into());
         assert_eq!(size, 584);
@@ -387,7 +456,7 @@ $ ls
 
     #[test]
     fn lightweight() {
-        let stdout = StdOut::from(INPUT);
+        let stdout = INPUT.parse::<StdOut>().unwrap();
         let fs = FileSystem::from(stdout);
         let lightweight = fs.find_lightweight_dirs(100_000);
         let names: Vec<&str> = lightweight.iter().map(|x| x.name.as_str()).collect();
@@ -400,4 +469,38 @@ $ ls
         assert_eq!(candidate.unwrap().0, 24_933_642);
         assert_eq!(candidate.unwrap().1.name, "d");
     }
+
+    #[test]
+    fn solution() {
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), 95_437);
+        assert_eq!(
+            Puzzle::part_two(Puzzle::parse(INPUT).unwrap()),
+            Ok(24_933_642)
+        );
+        let roomy = Puzzle::parse("$ cd /\n$ ls\n100 a.txt").unwrap();
+        assert_eq!(
+            Puzzle::part_two(roomy),
+            Err(Unsolvable::new(
+                "enough space is already unused, no directory needs deleting"
+            ))
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT
+            .replacen("$ ls", "$ dir", 1)
+            .parse::<StdOut>()
+            .unwrap_err();
+        assert_eq!(err.kind, TerminalError::UnknownCommand("dir".into()));
+        assert_eq!(err.span, Span::new(2, 3, 3));
+        let err = INPUT
+            .replace("29116 f", "29l16 f")
+            .parse::<StdOut>()
+            .unwrap_err();
+        assert_eq!(err.kind, TerminalError::InvalidSize("29l16".into()));
+        assert_eq!(err.span, Span::new(10, 1, 5));
+        let err = "$ cd".parse::<Command>().unwrap_err();
+        assert_eq!(err.kind, TerminalError::MalformedLine("$ cd".into()));
+    }
 }
@@ -1,185 +1,152 @@
-#![allow(dead_code)]
+use std::{fmt::Display, str::FromStr};
 
-use std::collections::{hash_map::Keys, HashMap};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Position {
-    x: usize,
-    y: usize,
-}
-
-#[derive(Debug, Clone, Copy)]
-pub struct Bounds {
-    width: usize,
-    height: usize,
-}
-
-impl From<(usize, usize)> for Position {
-    fn from(v: (usize, usize)) -> Self {
-        Self { x: v.0, y: v.1 }
-    }
-}
-
-impl Position {
-    fn any_edge(&self, bounds: Bounds) -> bool {
-        self.x == 0 || self.x == bounds.width - 1 || self.y == 0 || self.y == bounds.height - 1
-    }
-    fn edge(&self, toward: Direction, bounds: Bounds) -> bool {
-        match toward {
-            Direction::Left => self.x == 0,
-            Direction::Right => self.x == bounds.width - 1,
-            Direction::Top => self.y == 0,
-            Direction::Bottom => self.y == bounds.height - 1,
-        }
-    }
-    fn next(&self, toward: Direction) -> Position {
-        match toward {
-            Direction::Left => Position {
-                x: self.x - 1,
-                y: self.y,
-            },
-            Direction::Right => Position {
-                x: self.x + 1,
-                y: self.y,
-            },
-            Direction::Top => Position {
-                x: self.x,
-                y: self.y - 1,
-            },
-            Direction::Bottom => Position {
-                x: self.x,
-                y: self.y + 1,
-            },
-        }
-    }
-}
+use crate::{
+    diagnostics::Grammar,
+    geometry::{Direction, Point},
+    grid::{Grid, GridError},
+    parse::ParseError,
+    solution::Solution,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Size(usize);
 
-#[derive(Debug, Clone, Copy)]
-pub enum Direction {
-    Left,
-    Right,
-    Top,
-    Bottom,
-}
-
 #[derive(Debug)]
 pub struct Forest {
-    grid: HashMap<Position, Size>,
-    bounds: Bounds,
+    grid: Grid<Size>,
 }
 
 impl Forest {
-    pub fn positions(&self) -> Keys<Position, Size> {
-        self.grid.keys()
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        self.grid.positions()
     }
-    fn edge(&self, at: Position, toward: Direction) -> bool {
-        at.edge(toward, self.bounds)
+    fn any_edge(&self, at: Point) -> bool {
+        self.grid.neighbors(at, &Direction::ORTHOGONAL).count() < 4
     }
-    fn neighbor(&self, at: Position, toward: Direction) -> Option<&Size> {
-        if self.edge(at, toward) {
-            return None;
-        }
-        self.grid.get(&at.next(toward))
+    pub fn neighbor(&self, at: Point, toward: Direction) -> Option<&Size> {
+        self.grid.step(at, toward).map(|x| &self.grid[x])
     }
-    fn size(&self, at: Position) -> &Size {
-        self.grid.get(&at).as_ref().expect("no tree at position")
+    fn size(&self, at: Point) -> &Size {
+        self.grid.get(at).expect("no tree at position")
     }
-    fn visible(&self, at: Position, toward: Direction) -> bool {
-        if at.any_edge(self.bounds) {
+    fn visible(&self, at: Point, toward: Direction) -> bool {
+        if self.any_edge(at) {
             return true;
         }
-        let neighbors = self.neighbors(at, toward);
         let size = self.size(at);
-        neighbors.iter().all(|x| *x < size)
+        self.neighbors(at, toward).iter().all(|x| *x < size)
     }
-    pub fn visible_from_any_direction(&self, at: Position) -> bool {
-        if self.visible(at, Direction::Top) {
-            return true;
-        }
-        if self.visible(at, Direction::Bottom) {
-            return true;
-        }
-        if self.visible(at, Direction::Left) {
-            return true;
-        }
-        if self.visible(at, Direction::Right) {
-            return true;
-        }
-        false
+    pub fn visible_from_any_direction(&self, at: Point) -> bool {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .any(|toward| self.visible(at, toward))
     }
-    fn blocked_at(&self, at: Position, toward: Direction) -> usize {
+    fn blocked_at(&self, at: Point, toward: Direction) -> usize {
         let size = self.size(at);
-        let mut neighbors = vec![];
-        let mut next = at.clone();
-        let mut neighbor_size: &Size;
-        loop {
-            next = next.next(toward);
-            neighbor_size = self.grid.get(&next).unwrap();
-            neighbors.push(self.grid.get(&next).unwrap());
-            if neighbor_size >= size || next.edge(toward, self.bounds) {
+        let mut blocked = 0;
+        for next in self.grid.ray(at, toward) {
+            blocked += 1;
+            if self.grid[next] >= *size {
                 break;
             }
         }
-        neighbors.len()
+        blocked
     }
-    fn scenic_score(&self, at: Position) -> usize {
-        self.blocked_at(at, Direction::Top)
-            * self.blocked_at(at, Direction::Left)
-            * self.blocked_at(at, Direction::Right)
-            * self.blocked_at(at, Direction::Bottom)
+    fn scenic_score(&self, at: Point) -> usize {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .map(|toward| self.blocked_at(at, toward))
+            .product()
     }
+    /// trees on the edge see nothing one way, so score 0 and are skipped
     pub fn highest_scenic_score(&self) -> usize {
         self.positions()
-            .filter(|x| !x.any_edge(self.bounds))
-            .map(|x| self.scenic_score(*x))
+            .filter(|x| !self.any_edge(*x))
+            .map(|x| self.scenic_score(x))
             .max()
-            .expect("find max scenic score")
+            .unwrap_or(0)
     }
-    fn neighbors(&self, at: Position, toward: Direction) -> Vec<&Size> {
-        if self.edge(at, toward) {
-            return Vec::with_capacity(0);
-        }
-        let mut neighbors = vec![];
-        let mut next = at.clone();
-        loop {
-            next = next.next(toward);
-            neighbors.push(self.grid.get(&next).unwrap());
-            if next.edge(toward, self.bounds) {
-                break;
+    fn neighbors(&self, at: Point, toward: Direction) -> Vec<&Size> {
+        self.grid.ray(at, toward).map(|x| &self.grid[x]).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForestError {
+    Empty,
+    InvalidHeight(char),
+    RaggedRow { expected: usize, found: usize },
+}
+
+impl Display for ForestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "expected at least one row of trees"),
+            Self::InvalidHeight(c) => write!(f, "expected a tree height, found `{c}`"),
+            Self::RaggedRow { expected, found } => {
+                write!(f, "expected {expected} trees in the row, found {found}")
             }
         }
-        neighbors
     }
 }
 
-impl From<&str> for Forest {
-    fn from(v: &str) -> Self {
-        let mut forest = Self {
-            grid: HashMap::new(),
-            bounds: Bounds {
-                width: v.clone().lines().next().unwrap().len(),
-                height: v.clone().lines().count(),
-            },
-        };
-        for (y, line) in v.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                forest.grid.insert(
-                    Position { x, y },
-                    Size(c.to_digit(10).expect("should be a digit") as usize),
-                );
-            }
+impl Grammar for ForestError {
+    fn expected(&self) -> &'static str {
+        "<0-9>..., with rows of the same width"
+    }
+}
+
+impl From<GridError<char>> for ForestError {
+    fn from(v: GridError<char>) -> Self {
+        match v {
+            GridError::Empty => Self::Empty,
+            GridError::RaggedRow { expected, found } => Self::RaggedRow { expected, found },
+            GridError::InvalidCell(c) => Self::InvalidHeight(c),
         }
+    }
+}
+
+impl FromStr for Forest {
+    type Err = ParseError<ForestError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse(v, |c| c.to_digit(10).map(|x| Size(x as usize)).ok_or(c))
+            .map_err(|e| e.map(ForestError::from))?;
+        Ok(Self { grid })
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Treetop Tree House";
+
+    type Parsed = Forest;
+    type Error = ParseError<ForestError>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(forest: Self::Parsed) -> Self::One {
         forest
+            .positions()
+            .filter(|x| forest.visible_from_any_direction(*x))
+            .count()
+    }
+    fn part_two(forest: Self::Parsed) -> Self::Two {
+        forest.highest_scenic_score()
     }
 }
+
 #[cfg(test)]
 mod tests {
-    use super::{Direction, Forest};
+    use crate::{geometry::Direction, parse::Span, solution::Solution};
+
+    use super::{Forest, ForestError, Puzzle};
 
-    const INPUT: &'static str = "30373
+    const INPUT: &str = "30373
 25512
 65332
 33549
@@ -187,15 +154,15 @@ mod tests {
 
     #[test]
     fn parse() {
-        let forest = Forest::from(INPUT);
+        let forest = INPUT.parse::<Forest>().unwrap();
         println!("{forest:#?}");
-        assert_eq!(forest.bounds.width, 5);
-        assert_eq!(forest.bounds.height, 5);
+        assert_eq!(forest.grid.width(), 5);
+        assert_eq!(forest.grid.height(), 5);
     }
 
     #[test]
     fn neighbors() {
-        let forest = Forest::from(INPUT);
+        let forest = INPUT.parse::<Forest>().unwrap();
         let at = (0, 0);
         let neighbors = forest.neighbors(at.into(), Direction::Left);
         assert_eq!(neighbors.len(), 0);
@@ -206,98 +173,98 @@ mod tests {
         let neighbors = forest.neighbors(at.into(), Direction::Right);
         assert_eq!(neighbors.len(), 3);
         let at = (0, 3);
-        let neighbors = forest.neighbors(at.into(), Direction::Top);
+        let neighbors = forest.neighbors(at.into(), Direction::Up);
         assert_eq!(neighbors.len(), 3);
         let at = (0, 3);
-        let neighbors = forest.neighbors(at.into(), Direction::Bottom);
+        let neighbors = forest.neighbors(at.into(), Direction::Down);
         assert_eq!(neighbors.len(), 1);
     }
 
     #[test]
     fn visibility() {
-        let forest = Forest::from(INPUT);
+        let forest = INPUT.parse::<Forest>().unwrap();
         let at = (0, 0);
         assert!(forest.visible(at.into(), Direction::Left));
         assert!(forest.visible(at.into(), Direction::Right));
-        assert!(forest.visible(at.into(), Direction::Top));
-        assert!(forest.visible(at.into(), Direction::Bottom));
+        assert!(forest.visible(at.into(), Direction::Up));
+        assert!(forest.visible(at.into(), Direction::Down));
 
         let at = (0, 4);
         assert!(forest.visible(at.into(), Direction::Left));
         assert!(forest.visible(at.into(), Direction::Right));
-        assert!(forest.visible(at.into(), Direction::Top));
-        assert!(forest.visible(at.into(), Direction::Bottom));
+        assert!(forest.visible(at.into(), Direction::Up));
+        assert!(forest.visible(at.into(), Direction::Down));
 
         // top-left 5
         let at = (1, 1);
         assert!(forest.visible(at.into(), Direction::Left));
-        assert!(forest.visible(at.into(), Direction::Top));
+        assert!(forest.visible(at.into(), Direction::Up));
         assert!(!forest.visible(at.into(), Direction::Right));
-        assert!(!forest.visible(at.into(), Direction::Bottom));
+        assert!(!forest.visible(at.into(), Direction::Down));
 
         // top-middle 5
         let at = (2, 1);
-        assert!(forest.visible(at.into(), Direction::Top));
+        assert!(forest.visible(at.into(), Direction::Up));
         assert!(forest.visible(at.into(), Direction::Right));
-        assert!(!forest.visible(at.into(), Direction::Bottom));
+        assert!(!forest.visible(at.into(), Direction::Down));
         assert!(!forest.visible(at.into(), Direction::Left));
 
         // top-right 1
         let at = (3, 1);
-        assert!(!forest.visible(at.into(), Direction::Top));
+        assert!(!forest.visible(at.into(), Direction::Up));
         assert!(!forest.visible(at.into(), Direction::Right));
-        assert!(!forest.visible(at.into(), Direction::Bottom));
+        assert!(!forest.visible(at.into(), Direction::Down));
         assert!(!forest.visible(at.into(), Direction::Left));
 
         // left-middle 5
         let at = (1, 2);
         assert!(forest.visible(at.into(), Direction::Right));
-        assert!(!forest.visible(at.into(), Direction::Top));
-        assert!(!forest.visible(at.into(), Direction::Bottom));
+        assert!(!forest.visible(at.into(), Direction::Up));
+        assert!(!forest.visible(at.into(), Direction::Down));
         assert!(!forest.visible(at.into(), Direction::Left));
 
         // center 3
         let at = (2, 2);
-        assert!(!forest.visible(at.into(), Direction::Top));
+        assert!(!forest.visible(at.into(), Direction::Up));
         assert!(!forest.visible(at.into(), Direction::Right));
-        assert!(!forest.visible(at.into(), Direction::Bottom));
+        assert!(!forest.visible(at.into(), Direction::Down));
         assert!(!forest.visible(at.into(), Direction::Left));
 
         // right-middle 3
         let at = (3, 2);
         assert!(forest.visible(at.into(), Direction::Right));
-        assert!(!forest.visible(at.into(), Direction::Top));
-        assert!(!forest.visible(at.into(), Direction::Bottom));
+        assert!(!forest.visible(at.into(), Direction::Up));
+        assert!(!forest.visible(at.into(), Direction::Down));
         assert!(!forest.visible(at.into(), Direction::Left));
 
         // bottom-middle 5
         let at = (2, 3);
-        assert!(!forest.visible(at.into(), Direction::Top));
+        assert!(!forest.visible(at.into(), Direction::Up));
         assert!(!forest.visible(at.into(), Direction::Right));
-        assert!(forest.visible(at.into(), Direction::Bottom));
+        assert!(forest.visible(at.into(), Direction::Down));
         assert!(forest.visible(at.into(), Direction::Left));
 
         // bottom-left 3
         let at = (1, 3);
-        assert!(!forest.visible(at.into(), Direction::Top));
+        assert!(!forest.visible(at.into(), Direction::Up));
         assert!(!forest.visible(at.into(), Direction::Right));
-        assert!(!forest.visible(at.into(), Direction::Bottom));
+        assert!(!forest.visible(at.into(), Direction::Down));
         assert!(!forest.visible(at.into(), Direction::Left));
 
         // bottom-right 4
         let at = (3, 3);
-        assert!(!forest.visible(at.into(), Direction::Top));
+        assert!(!forest.visible(at.into(), Direction::Up));
         assert!(!forest.visible(at.into(), Direction::Right));
-        assert!(!forest.visible(at.into(), Direction::Bottom));
+        assert!(!forest.visible(at.into(), Direction::Down));
         assert!(!forest.visible(at.into(), Direction::Left));
     }
 
     #[test]
     fn visibility_from_any_direction() {
-        let forest = Forest::from(INPUT);
+        let forest = INPUT.parse::<Forest>().unwrap();
         let mut visible_ones = 0;
-        for key in forest.grid.keys() {
-            if forest.visible_from_any_direction(*key) {
+        for key in forest.positions() {
+            if forest.visible_from_any_direction(key) {
                 visible_ones += 1;
             }
         }
@@ -306,12 +273,43 @@ mod tests {
 
     #[test]
     fn scenic_score() {
-        let forest = Forest::from(INPUT);
+        let forest = INPUT.parse::<Forest>().unwrap();
         let at = (2, 3);
-        assert_eq!(forest.blocked_at(at.into(), Direction::Top), 2);
+        assert_eq!(forest.blocked_at(at.into(), Direction::Up), 2);
         assert_eq!(forest.blocked_at(at.into(), Direction::Left), 2);
-        assert_eq!(forest.blocked_at(at.into(), Direction::Bottom), 1);
+        assert_eq!(forest.blocked_at(at.into(), Direction::Down), 1);
         assert_eq!(forest.blocked_at(at.into(), Direction::Right), 2);
         assert_eq!(forest.scenic_score(at.into()), 8);
+        let edges = "12\n34".parse::<Forest>().unwrap();
+        assert_eq!(edges.highest_scenic_score(), 0);
+    }
+
+    #[test]
+    fn solution() {
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), 21);
+        assert_eq!(Puzzle::part_two(Puzzle::parse(INPUT).unwrap()), 8);
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT
+            .replace("33549", "33s49")
+            .parse::<Forest>()
+            .unwrap_err();
+        assert_eq!(err.kind, ForestError::InvalidHeight('s'));
+        assert_eq!(err.span, Span::new(4, 3, 1));
+        let err = INPUT
+            .replace("65332", "6533")
+            .parse::<Forest>()
+            .unwrap_err();
+        assert_eq!(
+            err.kind,
+            ForestError::RaggedRow {
+                expected: 5,
+                found: 4
+            }
+        );
+        assert_eq!(err.span, Span::new(3, 5, 1));
+        assert_eq!("\n".parse::<Forest>().unwrap_err().kind, ForestError::Empty);
     }
 }
@@ -1,17 +1,25 @@
+use std::str::FromStr;
+
 use super::{
     traits::{Adjacent, AdjacentPositions, Follow, Next, Overlap, Touching},
-    types::{Convolution, Direction, Knot, Knots, Motion, Motions, Position},
+    types::{Knot, Knots, Motion, MotionError, Motions},
+};
+use crate::{
+    diagnostics::Grammar,
+    geometry::{Direction, Point},
+    grid::Grid,
+    parse::{self, ParseError, Span},
 };
 use colored::Colorize;
 
-impl From<&Position> for Knot {
-    fn from(v: &Position) -> Self {
-        Self(v.clone())
+impl From<&Point> for Knot {
+    fn from(v: &Point) -> Self {
+        Self(*v)
     }
 }
 
-impl From<Position> for Knot {
-    fn from(v: Position) -> Self {
+impl From<Point> for Knot {
+    fn from(v: Point) -> Self {
         Self(v)
     }
 }
@@ -26,25 +34,18 @@ impl<const LENGTH: usize> Knots<LENGTH> {
             let mut next;
             let mut moved = None;
             let count = knots.len();
-            let mut projection: Knot = knots
-                .get(0)
-                .unwrap()
-                .clone()
-                .0
-                .next(&motion.direction.into())
-                .into();
-            *knots.get_mut(0).unwrap() = projection.clone();
+            let mut projection: Knot = knots.first().unwrap().0.next(&motion.direction).into();
+            *knots.get_mut(0).unwrap() = projection;
             for current in 0..(count - 1) {
                 next = current + 1;
-                leader = knots.get(current).unwrap().clone();
-                follower = knots.get(next).unwrap().clone();
-                let lead: &Convolution = &motion.direction.into();
-                projection = leader.0.clone().into();
+                leader = *knots.get(current).unwrap();
+                follower = *knots.get(next).unwrap();
+                projection = leader.0.into();
                 if !follower.touching(&projection) {
                     let follow = follower.follow(&leader);
                     *knots.get_mut(next).unwrap() = follower.0.next(&follow).into();
                     if next == (count - 1) {
-                        moved = Some(knots.get(next).unwrap().clone().0);
+                        moved = Some(knots.get(next).unwrap().0);
                     }
                 }
             }
@@ -53,20 +54,34 @@ impl<const LENGTH: usize> Knots<LENGTH> {
             }
         }
     }
-    fn record_tail_visited(&mut self, at: &Position) {
+    fn record_tail_visited(&mut self, at: &Point) {
         if !self.visited.contains(&at.into()) {
-            self.visited.push(at.clone().into());
+            self.visited.push((*at).into());
         }
     }
     pub fn do_motions(&mut self, motions: &Motions) {
-        self.visited.push(self.knots.last().unwrap().clone());
+        self.visited.push(*self.knots.last().unwrap());
         for motion in motions.0.iter() {
-            self.do_motion(&motion);
+            self.do_motion(motion);
         }
     }
     pub fn total_tail_visited(&self) -> usize {
         self.visited.len()
     }
+    /// positions visited by the tail, drawn with `#` over the smallest grid holding them
+    pub fn trail(&self) -> Grid<char> {
+        let xs = self.visited.iter().map(|x| x.0.x);
+        let ys = self.visited.iter().map(|x| x.0.y);
+        let (left, right) = (xs.clone().min().unwrap_or(0), xs.max().unwrap_or(0));
+        let (top, bottom) = (ys.clone().min().unwrap_or(0), ys.max().unwrap_or(0));
+        let width = (right - left + 1) as usize;
+        let height = (bottom - top + 1) as usize;
+        let mut trail = Grid::new(width, height, '.');
+        for knot in self.visited.iter() {
+            trail[Point::new(knot.0.x - left, knot.0.y - top)] = '#';
+        }
+        trail
+    }
 }
 
 impl<const LENGTH: usize> Default for Knots<LENGTH> {
@@ -78,52 +93,6 @@ impl<const LENGTH: usize> Default for Knots<LENGTH> {
     }
 }
 
-impl Default for Position {
-    fn default() -> Self {
-        Self { x: 0, y: 0 }
-    }
-}
-
-impl Default for Knot {
-    fn default() -> Self {
-        Self(Position::default())
-    }
-}
-
-impl std::fmt::Display for Position {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "x: {} y: {}", self.x, self.y)
-    }
-}
-
-impl std::fmt::Display for Direction {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let symbol = match self {
-            Self::Up => "⬆️".to_string(),
-            Self::Down => "⬇️".to_string(),
-            Self::Left => "⬅️".to_string(),
-            Self::Right => "➡️".to_string(),
-        };
-        write!(f, "{}", symbol)
-    }
-}
-
-impl std::fmt::Display for Convolution {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let symbol = match self {
-            Self::Up => "⬆️".to_string(),
-            Self::Down => "⬇️".to_string(),
-            Self::Left => "⬅️".to_string(),
-            Self::Right => "➡️".to_string(),
-            Self::UpLeft => "↖️".to_string(),
-            Self::UpRight => "↗️".to_string(),
-            Self::DownLeft => "↙️".to_string(),
-            Self::DownRight => "↘️".to_string(),
-        };
-        write!(f, "{}", symbol)
-    }
-}
-
 impl std::fmt::Display for Knot {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -161,79 +130,84 @@ impl std::fmt::Display for Motion {
         write!(
             f,
             "{} {}",
-            self.direction.as_ref().yellow(),
+            letter(self.direction).yellow(),
             self.steps.to_string().yellow()
         )
     }
 }
 
-impl AsRef<str> for Direction {
-    fn as_ref(&self) -> &str {
-        match self {
-            Self::Up => "U",
-            Self::Down => "D",
-            Self::Left => "L",
-            Self::Right => "R",
-        }
+impl AsRef<Point> for Knot {
+    fn as_ref(&self) -> &Point {
+        &self.0
     }
 }
 
-impl AsRef<Position> for Knot {
-    fn as_ref(&self) -> &Position {
-        &self.0
+impl std::fmt::Display for MotionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownDirection(v) => write!(f, "expected one of R, L, U, D, found `{v}`"),
+            Self::InvalidSteps(v) => write!(f, "expected a number of steps, found `{v}`"),
+            Self::MissingSteps => write!(f, "expected a number of steps"),
+            Self::TrailingInput(v) => write!(f, "unexpected `{v}` after the steps"),
+        }
     }
 }
 
-impl From<(isize, isize)> for Position {
-    fn from(v: (isize, isize)) -> Self {
-        Self { x: v.0, y: v.1 }
+impl Grammar for MotionError {
+    fn expected(&self) -> &'static str {
+        "<R|L|U|D> <n>"
     }
 }
 
-impl From<Direction> for Convolution {
-    fn from(v: Direction) -> Self {
-        match v {
-            Direction::Up => Self::Up,
-            Direction::Down => Self::Down,
-            Direction::Left => Self::Left,
-            Direction::Right => Self::Right,
-        }
+fn direction(v: &str) -> Result<Direction, MotionError> {
+    match v {
+        "R" => Ok(Direction::Right),
+        "L" => Ok(Direction::Left),
+        "U" => Ok(Direction::Up),
+        "D" => Ok(Direction::Down),
+        _ => Err(MotionError::UnknownDirection(v.to_string())),
     }
 }
 
-impl From<&str> for Direction {
-    fn from(v: &str) -> Self {
-        match v {
-            "R" => Self::Right,
-            "L" => Self::Left,
-            "U" => Self::Up,
-            "D" => Self::Down,
-            _ => panic!("unknown direction"),
-        }
+fn letter(v: Direction) -> &'static str {
+    match v {
+        Direction::Up => "U",
+        Direction::Down => "D",
+        Direction::Left => "L",
+        _ => "R",
     }
 }
 
-impl From<&str> for Motion {
-    fn from(v: &str) -> Self {
-        let parts: Vec<&str> = v.split(' ').collect();
-        assert!(parts.len() == 2);
-        let direction = Direction::from(*parts.first().expect("direction"));
+impl FromStr for Motion {
+    type Err = ParseError<MotionError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let mut parts = v.split_whitespace();
+        let direction = parts.next().unwrap_or_default();
+        let direction =
+            self::direction(direction).map_err(|e| ParseError::new(e, Span::of(v, direction)))?;
         let steps = parts
-            .get(1)
-            .expect("steps")
-            .parse()
-            .expect("should be a digit");
-        Self { steps, direction }
+            .next()
+            .ok_or_else(|| ParseError::new(MotionError::MissingSteps, Span::after(v)))?;
+        let steps = steps.parse().map_err(|_| {
+            ParseError::new(
+                MotionError::InvalidSteps(steps.to_string()),
+                Span::of(v, steps),
+            )
+        })?;
+        if let Some(extra) = parts.next() {
+            return Err(ParseError::new(
+                MotionError::TrailingInput(extra.to_string()),
+                Span::of(v, extra),
+            ));
+        }
+        Ok(Self { steps, direction })
     }
 }
 
-impl From<&str> for Motions {
-    fn from(v: &str) -> Self {
-        let mut movements = vec![];
-        for line in v.lines() {
-            movements.push(Motion::from(line));
-        }
-        Self(movements)
+impl FromStr for Motions {
+    type Err = ParseError<MotionError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        Ok(Self(parse::lines(v)?))
     }
 }
 
@@ -242,16 +216,7 @@ where
     T: Next + Sized,
 {
     fn adjacent_positions(&self) -> Vec<Self> {
-        vec![
-            self.next(&Convolution::Up),
-            self.next(&Convolution::Down),
-            self.next(&Convolution::Left),
-            self.next(&Convolution::Right),
-            self.next(&Convolution::UpLeft),
-            self.next(&Convolution::UpRight),
-            self.next(&Convolution::DownLeft),
-            self.next(&Convolution::DownRight),
-        ]
+        Direction::ALL.iter().map(|x| self.next(x)).collect()
     }
 }
 
@@ -264,48 +229,15 @@ where
     }
 }
 
-impl Next for Position {
-    fn next(&self, toward: &Convolution) -> Position {
-        match toward {
-            Convolution::Up => Position {
-                x: self.x,
-                y: self.y - 1,
-            },
-            Convolution::Down => Position {
-                x: self.x,
-                y: self.y + 1,
-            },
-            Convolution::Left => Position {
-                x: self.x - 1,
-                y: self.y,
-            },
-            Convolution::Right => Position {
-                x: self.x + 1,
-                y: self.y,
-            },
-            Convolution::UpLeft => Position {
-                x: self.x - 1,
-                y: self.y - 1,
-            },
-            Convolution::UpRight => Position {
-                x: self.x + 1,
-                y: self.y - 1,
-            },
-            Convolution::DownLeft => Position {
-                x: self.x - 1,
-                y: self.y + 1,
-            },
-            Convolution::DownRight => Position {
-                x: self.x + 1,
-                y: self.y + 1,
-            },
-        }
+impl Next for Point {
+    fn next(&self, toward: &Direction) -> Point {
+        self.step(*toward)
     }
 }
 
-impl Overlap for Position {
+impl Overlap for Point {
     fn overlap(&self, related: &Self) -> bool {
-        self.x == related.x && self.y == related.y
+        self == related
     }
 }
 
@@ -315,9 +247,9 @@ impl Overlap for Knot {
     }
 }
 
-impl Adjacent for Position {
+impl Adjacent for Point {
     fn adjacent(&self, related: &Self) -> bool {
-        self.adjacent_positions().iter().any(|x| x.overlap(related))
+        self.chebyshev(related) == 1
     }
 }
 
@@ -327,34 +259,15 @@ impl Adjacent for Knot {
     }
 }
 
-impl Follow for Position {
-    fn follow(&self, leader: &Self) -> Convolution {
-        match true {
-            // two steps ahead
-            _ if self.y == leader.y && (self.x + 2) == leader.x => Convolution::Right,
-            _ if self.y == leader.y && (self.x - 2) == leader.x => Convolution::Left,
-            _ if self.x == leader.x && (self.y + 2) == leader.y => Convolution::Down,
-            _ if self.x == leader.x && (self.y - 2) == leader.y => Convolution::Up,
-            // diagonal
-            _ if (self.x + 1) == leader.x && (self.y - 2) == leader.y => Convolution::UpRight,
-            _ if (self.x + 2) == leader.x && (self.y - 1) == leader.y => Convolution::UpRight,
-            _ if (self.x + 2) == leader.x && (self.y - 2) == leader.y => Convolution::UpRight,
-            _ if (self.x - 1) == leader.x && (self.y - 2) == leader.y => Convolution::UpLeft,
-            _ if (self.x - 2) == leader.x && (self.y - 1) == leader.y => Convolution::UpLeft,
-            _ if (self.x - 2) == leader.x && (self.y - 2) == leader.y => Convolution::UpLeft,
-            _ if (self.x + 1) == leader.x && (self.y + 2) == leader.y => Convolution::DownRight,
-            _ if (self.x + 2) == leader.x && (self.y + 1) == leader.y => Convolution::DownRight,
-            _ if (self.x + 2) == leader.x && (self.y + 2) == leader.y => Convolution::DownRight,
-            _ if (self.x - 1) == leader.x && (self.y + 2) == leader.y => Convolution::DownLeft,
-            _ if (self.x - 2) == leader.x && (self.y + 1) == leader.y => Convolution::DownLeft,
-            _ if (self.x - 2) == leader.x && (self.y + 2) == leader.y => Convolution::DownLeft,
-            _ => panic!("should not happen"),
-        }
+impl Follow for Point {
+    fn follow(&self, leader: &Self) -> Direction {
+        // one step toward the leader, diagonally when not in line with it
+        Direction::try_from((*leader - *self).signum()).expect("leader out of reach")
     }
 }
 
 impl Follow for Knot {
-    fn follow(&self, leader: &Self) -> Convolution {
+    fn follow(&self, leader: &Self) -> Direction {
         self.0.follow(&leader.0)
     }
 }
@@ -1,16 +1,25 @@
-#![allow(dead_code, unused_variables)]
-
 pub mod impls;
+pub mod solution;
 pub mod traits;
 pub mod types;
 
+pub use solution::Puzzle;
+
 #[cfg(test)]
 mod tests {
     #![allow(unused_imports)]
 
-    use crate::day_9::types::{Direction, Knots, Motion, Motions};
+    use crate::{
+        day_9::{
+            types::{Knots, Motion, MotionError, Motions},
+            Puzzle,
+        },
+        geometry::Direction,
+        parse::Span,
+        solution::Solution,
+    };
 
-    const INPUT: &'static str = "R 4
+    const INPUT: &str = "R 4
 U 4
 L 3
 D 1
@@ -18,12 +27,20 @@ R 4
 D 1
 L 5
 R 2";
+    const LARGER_INPUT: &str = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20";
 
     #[test]
     fn parse() {
-        let motions = Motions::from(INPUT);
+        let motions = INPUT.parse::<Motions>().unwrap();
         assert_eq!(motions.0.len(), 8);
-        let motion = motions.0.get(0).unwrap();
+        let motion = motions.0.first().unwrap();
         assert_eq!(
             motion,
             &Motion {
@@ -48,4 +65,55 @@ R 2";
             }
         );
     }
+
+    #[test]
+    fn duo() {
+        let motions = INPUT.parse::<Motions>().unwrap();
+        let mut knots: Knots<2> = Knots::default();
+        knots.do_motions(&motions);
+        assert_eq!(knots.visited.len(), 13);
+        assert_eq!(
+            knots.trail().to_string(),
+            "..##.
+...##
+.####
+....#
+####."
+        );
+    }
+
+    #[test]
+    fn snake() {
+        let motions = INPUT.parse::<Motions>().unwrap();
+        let mut knots: Knots<10> = Knots::default();
+        knots.do_motions(&motions);
+        assert_eq!(knots.visited.len(), 1);
+
+        let motions = LARGER_INPUT.parse::<Motions>().unwrap();
+        let mut knots: Knots<10> = Knots::default();
+        knots.do_motions(&motions);
+        assert_eq!(knots.visited.len(), 36);
+    }
+
+    #[test]
+    fn solution() {
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), 13);
+        assert_eq!(Puzzle::part_two(Puzzle::parse(LARGER_INPUT).unwrap()), 36);
+    }
+
+    #[test]
+    fn invalid() {
+        let err = INPUT.replace("L 3", "X 3").parse::<Motions>().unwrap_err();
+        assert_eq!(err.kind, MotionError::UnknownDirection("X".into()));
+        assert_eq!(err.span, Span::new(3, 1, 1));
+        let err = INPUT
+            .replace("D 1\nR 4", "D 1\nR four")
+            .parse::<Motions>()
+            .unwrap_err();
+        assert_eq!(err.kind, MotionError::InvalidSteps("four".into()));
+        assert_eq!(err.span, Span::new(5, 3, 4));
+        let err = "U".parse::<Motion>().unwrap_err();
+        assert_eq!(err.kind, MotionError::MissingSteps);
+        assert_eq!(err.span, Span::new(1, 2, 1));
+    }
 }
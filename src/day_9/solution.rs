@@ -0,0 +1,29 @@
+use crate::{parse::ParseError, solution::Solution};
+
+use super::types::{Knots, MotionError, Motions};
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Rope Bridge";
+
+    type Parsed = Motions;
+    type Error = ParseError<MotionError>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        input.parse()
+    }
+    fn part_one(motions: Self::Parsed) -> Self::One {
+        let mut knots: Knots<2> = Knots::default();
+        knots.do_motions(&motions);
+        knots.total_tail_visited()
+    }
+    fn part_two(motions: Self::Parsed) -> Self::Two {
+        let mut knots: Knots<10> = Knots::default();
+        knots.do_motions(&motions);
+        knots.total_tail_visited()
+    }
+}
@@ -1,7 +1,7 @@
-use super::types::Convolution;
+use crate::geometry::Direction;
 
 pub trait Next {
-    fn next(&self, toward: &Convolution) -> Self;
+    fn next(&self, toward: &Direction) -> Self;
 }
 
 pub trait Overlap {
@@ -24,5 +24,5 @@ where
 }
 
 pub trait Follow {
-    fn follow(&self, leader: &Self) -> Convolution;
+    fn follow(&self, leader: &Self) -> Direction;
 }
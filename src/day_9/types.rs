@@ -1,11 +1,7 @@
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Position {
-    pub x: isize,
-    pub y: isize,
-}
+use crate::geometry::{Direction, Point};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Knot(pub Position);
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Knot(pub Point);
 
 pub struct Knots<const LENGTH: usize> {
     pub knots: [Knot; LENGTH],
@@ -21,22 +17,10 @@ pub struct Motion {
 #[derive(Debug)]
 pub struct Motions(pub Vec<Motion>);
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[derive(Debug, PartialEq)]
-pub enum Convolution {
-    Up,
-    Down,
-    Left,
-    Right,
-    UpLeft,
-    UpRight,
-    DownLeft,
-    DownRight,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MotionError {
+    UnknownDirection(String),
+    InvalidSteps(String),
+    MissingSteps,
+    TrailingInput(String),
 }
@@ -0,0 +1,145 @@
+use std::fmt::Display;
+
+use colored::Colorize;
+
+use crate::parse::{ParseError, Span};
+
+/// what a day expects from its input, implemented by each kind of parse error
+pub trait Grammar {
+    /// expected shape of the offending line, e.g. `move <n> from <n> to <n>`
+    fn expected(&self) -> &'static str;
+    /// how to fix the input, when the error itself doesn't say it all
+    fn hint(&self) -> Option<String> {
+        None
+    }
+}
+
+/// turns an error into a [`Diagnostic`] pointing at the puzzle input
+pub trait Diagnose {
+    fn diagnose(&self, origin: &str, input: &str) -> Diagnostic;
+}
+
+/// parse error rendered along with the offending line of input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// where the input comes from, e.g. `day 5`
+    pub origin: String,
+    pub span: Span,
+    pub message: String,
+    /// offending line, empty when the input ended too early
+    pub line: String,
+    pub expected: &'static str,
+    pub hint: Option<String>,
+}
+
+impl<K: Grammar + Display> Diagnose for ParseError<K> {
+    fn diagnose(&self, origin: &str, input: &str) -> Diagnostic {
+        Diagnostic {
+            origin: origin.to_string(),
+            span: self.span,
+            message: self.kind.to_string(),
+            line: input
+                .lines()
+                .nth(self.span.line.saturating_sub(1))
+                .unwrap_or_default()
+                .to_string(),
+            expected: self.kind.expected(),
+            hint: self.kind.hint(),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let number = self.span.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let bar = "|".blue().bold();
+        writeln!(f, "{}: {}", "error".red().bold(), self.message.bold())?;
+        writeln!(
+            f,
+            "{gutter}{} {}, {}",
+            "-->".blue().bold(),
+            self.origin,
+            self.span
+        )?;
+        writeln!(f, "{gutter} {bar}")?;
+        writeln!(f, "{} {bar} {}", number.blue().bold(), self.line)?;
+        writeln!(
+            f,
+            "{gutter} {bar} {}{}",
+            " ".repeat(self.span.column.saturating_sub(1)),
+            "^".repeat(self.span.len).red().bold()
+        )?;
+        let mut expected = self.expected.lines();
+        write!(
+            f,
+            "{gutter} {} {}: {}",
+            "=".blue().bold(),
+            "expected".bold(),
+            expected.next().unwrap_or_default()
+        )?;
+        for line in expected {
+            write!(f, "\n{gutter}             {line}")?;
+        }
+        if let Some(hint) = &self.hint {
+            write!(
+                f,
+                "\n{gutter} {} {}: {}",
+                "=".blue().bold(),
+                "hint".cyan().bold(),
+                hint.cyan()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        day_5::{CargoError, Moves},
+        parse::{ParseError, Span},
+    };
+
+    use super::Diagnose;
+
+    const INPUT: &str = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to x";
+
+    #[test]
+    fn render() {
+        colored::control::set_override(false);
+        let err = INPUT.parse::<Moves>().unwrap_err();
+        let diagnostic = err.diagnose("day 5", INPUT);
+        assert_eq!(diagnostic.line, "move 3 from 1 to x");
+        assert_eq!(
+            diagnostic.to_string(),
+            "error: malformed move `move 3 from 1 to x`
+ --> day 5, line 7, column 18
+  |
+7 | move 3 from 1 to x
+  |                  ^
+  = expected: move <n> from <n> to <n>"
+        );
+    }
+
+    #[test]
+    fn hint() {
+        colored::control::set_override(false);
+        let err = ParseError::new(CargoError::UnknownStack(4), Span::new(6, 18, 1));
+        let diagnostic = err.diagnose("day 5", INPUT);
+        assert_eq!(diagnostic.line, "move 1 from 2 to 1");
+        assert!(diagnostic
+            .to_string()
+            .ends_with("  |                  ^\n  = expected: move <n> from <n> to <n>\n  = hint: stacks are numbered from 1, as labelled under the drawing"));
+        let err = ParseError::new(CargoError::UnknownStack(4), Span::new(12, 1, 1));
+        assert_eq!(err.diagnose("day 5", INPUT).line, "");
+    }
+}
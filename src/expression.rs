@@ -0,0 +1,221 @@
+use std::{fmt::Display, rc::Rc, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Operator {
+    /// `left <operator> right`, if it neither overflows nor divides by zero
+    pub fn apply(&self, left: i64, right: i64) -> Option<i64> {
+        match self {
+            Self::Add => left.checked_add(right),
+            Self::Sub => left.checked_sub(right),
+            Self::Mul => left.checked_mul(right),
+            Self::Div => left.checked_div(right),
+        }
+    }
+}
+
+impl FromStr for Operator {
+    type Err = String;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        match v {
+            "+" => Ok(Self::Add),
+            "-" => Ok(Self::Sub),
+            "*" => Ok(Self::Mul),
+            "/" => Ok(Self::Div),
+            _ => Err(v.to_string()),
+        }
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+        };
+        write!(f, "{symbol}")
+    }
+}
+
+/// arithmetic on integers and named variables, where operations may share operands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Number(i64),
+    Variable(String),
+    Binary(Operator, Rc<Expr>, Rc<Expr>),
+}
+
+impl Expr {
+    pub fn binary(operator: Operator, left: Self, right: Self) -> Self {
+        Self::Binary(operator, Rc::new(left), Rc::new(right))
+    }
+    /// operation on two expressions, replaced by its result when both are numbers
+    pub fn fold(operator: Operator, left: impl Into<Rc<Self>>, right: impl Into<Rc<Self>>) -> Self {
+        let (left, right) = (left.into(), right.into());
+        if let (Self::Number(a), Self::Number(b)) = (left.as_ref(), right.as_ref()) {
+            if let Some(v) = operator.apply(*a, *b) {
+                return Self::Number(v);
+            }
+        }
+        Self::Binary(operator, left, right)
+    }
+    /// value of the expression, with `lookup` giving the value of each variable
+    pub fn evaluate(&self, lookup: &impl Fn(&str) -> Option<i64>) -> Option<i64> {
+        match self {
+            Self::Number(v) => Some(*v),
+            Self::Variable(name) => lookup(name),
+            Self::Binary(operator, left, right) => {
+                operator.apply(left.evaluate(lookup)?, right.evaluate(lookup)?)
+            }
+        }
+    }
+    pub fn contains(&self, variable: &str) -> bool {
+        match self {
+            Self::Number(_) => false,
+            Self::Variable(name) => name == variable,
+            Self::Binary(_, left, right) => left.contains(variable) || right.contains(variable),
+        }
+    }
+    /// same expression with every operation on numbers only replaced by its result
+    pub fn simplify(self) -> Self {
+        match self {
+            Self::Binary(operator, left, right) => Self::fold(
+                operator,
+                Rc::unwrap_or_clone(left).simplify(),
+                Rc::unwrap_or_clone(right).simplify(),
+            ),
+            other => other,
+        }
+    }
+    /// value of the only variable for the expression to equal `target`, undoing one operation at a time
+    /// from the outside in, where every operation needs a side without variables once simplified
+    pub fn solve(&self, target: i64) -> Option<i64> {
+        // simplified once, every side without variables is a number rather than evaluated at each level
+        self.clone().simplify().invert(target)
+    }
+    /// `solve` on a simplified expression
+    fn invert(&self, target: i64) -> Option<i64> {
+        match self {
+            Self::Number(_) => None,
+            Self::Variable(_) => Some(target),
+            Self::Binary(operator, left, right) => {
+                let known = |x: &Self| match x {
+                    Self::Number(v) => Some(*v),
+                    _ => None,
+                };
+                match (known(left), known(right)) {
+                    // x op c = target
+                    (None, Some(c)) => {
+                        let x = match operator {
+                            Operator::Add => target.checked_sub(c)?,
+                            Operator::Sub => target.checked_add(c)?,
+                            Operator::Mul => exact(target, c)?,
+                            Operator::Div => target.checked_mul(c)?,
+                        };
+                        left.invert(x)
+                    }
+                    // c op x = target
+                    (Some(c), None) => {
+                        let x = match operator {
+                            Operator::Add => target.checked_sub(c)?,
+                            Operator::Sub => c.checked_sub(target)?,
+                            Operator::Mul => exact(target, c)?,
+                            Operator::Div => exact(c, target)?,
+                        };
+                        right.invert(x)
+                    }
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+/// `a / b` when `b` divides `a`
+fn exact(a: i64, b: i64) -> Option<i64> {
+    (b != 0 && a % b == 0).then(|| a / b)
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(v) => write!(f, "{v}"),
+            Self::Variable(name) => write!(f, "{name}"),
+            Self::Binary(operator, left, right) => write!(f, "({left} {operator} {right})"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Expr, Operator};
+
+    fn x() -> Expr {
+        Expr::Variable("x".into())
+    }
+
+    fn n(v: i64) -> Expr {
+        Expr::Number(v)
+    }
+
+    #[test]
+    fn evaluate() {
+        let expr = Expr::binary(Operator::Mul, Expr::binary(Operator::Add, x(), n(3)), n(4));
+        assert_eq!(expr.to_string(), "((x + 3) * 4)");
+        assert_eq!(expr.evaluate(&|_| Some(2)), Some(20));
+        assert_eq!(expr.evaluate(&|_| None), None);
+        assert_eq!(
+            Expr::binary(Operator::Div, n(1), n(0)).evaluate(&|_| None),
+            None
+        );
+        assert!(expr.contains("x") && !expr.contains("y"));
+    }
+
+    #[test]
+    fn simplify() {
+        let constant = Expr::binary(
+            Operator::Sub,
+            n(10),
+            Expr::binary(Operator::Mul, n(2), n(3)),
+        );
+        assert_eq!(constant.simplify(), n(4));
+        let expr = Expr::binary(Operator::Add, x(), Expr::binary(Operator::Div, n(9), n(3)));
+        assert_eq!(expr.simplify().to_string(), "(x + 3)");
+        assert_eq!(Expr::fold(Operator::Mul, n(6), n(7)), n(42));
+        assert_eq!(Expr::fold(Operator::Div, n(6), n(0)).to_string(), "(6 / 0)");
+        assert_eq!(Expr::fold(Operator::Sub, x(), n(7)).to_string(), "(x - 7)");
+    }
+
+    #[test]
+    fn solve() {
+        // (4 + 2 * (x - 3)) / 4 = 150
+        let expr = Expr::binary(
+            Operator::Div,
+            Expr::binary(
+                Operator::Add,
+                n(4),
+                Expr::binary(Operator::Mul, n(2), Expr::binary(Operator::Sub, x(), n(3))),
+            ),
+            n(4),
+        );
+        assert_eq!(expr.solve(150), Some(301));
+        assert_eq!(expr.evaluate(&|_| Some(301)), Some(150));
+        let inverse = Expr::binary(
+            Operator::Sub,
+            n(10),
+            Expr::binary(Operator::Div, n(100), x()),
+        );
+        assert_eq!(inverse.solve(6), Some(25));
+        assert_eq!(inverse.solve(7), None);
+        assert_eq!(Expr::binary(Operator::Add, x(), x()).solve(4), None);
+        assert_eq!(n(3).solve(3), None);
+    }
+}
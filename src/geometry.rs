@@ -0,0 +1,358 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub},
+};
+
+/// position on a plane, where `y` grows downward as it does in puzzle inputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+/// difference between two points
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Offset {
+    pub x: isize,
+    pub y: isize,
+}
+
+/// position in space, for puzzles that leave the plane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+/// step toward one of the 8 neighbors of a point, `Up` being toward decreasing `y`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Point {
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+    /// neighbor toward a direction
+    pub fn step(&self, toward: Direction) -> Self {
+        *self + toward.offset()
+    }
+    /// neighbors among `directions`, e.g. [`Direction::ORTHOGONAL`] or [`Direction::ALL`]
+    pub fn neighbors<'a>(&'a self, directions: &'a [Direction]) -> impl Iterator<Item = Self> + 'a {
+        directions.iter().map(|x| self.step(*x))
+    }
+    /// distance when moving along axes only
+    pub fn manhattan(&self, other: &Self) -> usize {
+        (*other - *self).manhattan()
+    }
+    /// distance when moving diagonally costs the same as along axes
+    pub fn chebyshev(&self, other: &Self) -> usize {
+        (*other - *self).chebyshev()
+    }
+}
+
+impl Point3 {
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Self { x, y, z }
+    }
+    /// the 6 neighbors sharing a face with this one
+    pub fn faces(&self) -> impl Iterator<Item = Self> {
+        let Self { x, y, z } = *self;
+        [
+            Self::new(x - 1, y, z),
+            Self::new(x + 1, y, z),
+            Self::new(x, y - 1, z),
+            Self::new(x, y + 1, z),
+            Self::new(x, y, z - 1),
+            Self::new(x, y, z + 1),
+        ]
+        .into_iter()
+    }
+    pub fn manhattan(&self, other: &Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+    /// smallest of each coordinate
+    pub fn least(&self, other: &Self) -> Self {
+        Self::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+    /// largest of each coordinate
+    pub fn greatest(&self, other: &Self) -> Self {
+        Self::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+    pub fn dot(&self, other: &Self) -> isize {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+impl Offset {
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+    pub fn manhattan(&self) -> usize {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+    pub fn chebyshev(&self) -> usize {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+    /// at most one step along each axis, in the same general direction
+    pub fn signum(&self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+    /// quarter turn clockwise, as seen on screen
+    pub fn rotate_right(&self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+    /// quarter turn counterclockwise, as seen on screen
+    pub fn rotate_left(&self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+}
+
+impl Direction {
+    /// neighbors sharing a side
+    pub const ORTHOGONAL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+    /// neighbors sharing a side or a corner
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    pub fn offset(&self) -> Offset {
+        match self {
+            Self::Up => Offset::new(0, -1),
+            Self::Down => Offset::new(0, 1),
+            Self::Left => Offset::new(-1, 0),
+            Self::Right => Offset::new(1, 0),
+            Self::UpLeft => Offset::new(-1, -1),
+            Self::UpRight => Offset::new(1, -1),
+            Self::DownLeft => Offset::new(-1, 1),
+            Self::DownRight => Offset::new(1, 1),
+        }
+    }
+    /// quarter turn clockwise
+    pub fn turn_right(&self) -> Self {
+        Self::try_from(self.offset().rotate_right()).expect("unit offset")
+    }
+    /// quarter turn counterclockwise
+    pub fn turn_left(&self) -> Self {
+        Self::try_from(self.offset().rotate_left()).expect("unit offset")
+    }
+    pub fn opposite(&self) -> Self {
+        Self::try_from(-self.offset()).expect("unit offset")
+    }
+}
+
+impl TryFrom<Offset> for Direction {
+    type Error = Offset;
+    /// direction of a single step, if `v` is one
+    fn try_from(v: Offset) -> Result<Self, Self::Error> {
+        Self::ALL.into_iter().find(|x| x.offset() == v).ok_or(v)
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((x, y): (isize, isize)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<(isize, isize, isize)> for Point3 {
+    fn from((x, y, z): (isize, isize, isize)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl From<(isize, isize)> for Offset {
+    fn from((x, y): (isize, isize)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<Direction> for Offset {
+    fn from(v: Direction) -> Self {
+        v.offset()
+    }
+}
+
+impl Add<Offset> for Point {
+    type Output = Point;
+    fn add(self, rhs: Offset) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign<Offset> for Point {
+    fn add_assign(&mut self, rhs: Offset) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Offset;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Offset::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add for Offset {
+    type Output = Offset;
+    fn add(self, rhs: Self) -> Self::Output {
+        Offset::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Mul<isize> for Offset {
+    type Output = Offset;
+    fn mul(self, rhs: isize) -> Self::Output {
+        Offset::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Offset {
+    type Output = Offset;
+    fn neg(self) -> Self::Output {
+        Offset::new(-self.x, -self.y)
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+    fn add(self, rhs: Self) -> Self::Output {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul<isize> for Point3 {
+    type Output = Point3;
+    fn mul(self, rhs: isize) -> Self::Output {
+        Point3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+    fn neg(self) -> Self::Output {
+        self * -1
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "x: {} y: {}", self.x, self.y)
+    }
+}
+
+impl Display for Point3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "x: {} y: {} z: {}", self.x, self.y, self.z)
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            Self::Up => "⬆️",
+            Self::Down => "⬇️",
+            Self::Left => "⬅️",
+            Self::Right => "➡️",
+            Self::UpLeft => "↖️",
+            Self::UpRight => "↗️",
+            Self::DownLeft => "↙️",
+            Self::DownRight => "↘️",
+        };
+        write!(f, "{symbol}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Offset, Point, Point3};
+
+    #[test]
+    fn distances() {
+        let a = Point::new(1, -2);
+        let b = Point::new(-3, 4);
+        assert_eq!(b - a, Offset::new(-4, 6));
+        assert_eq!(a.manhattan(&b), 10);
+        assert_eq!(a.chebyshev(&b), 6);
+        assert_eq!(a.chebyshev(&a.step(Direction::DownLeft)), 1);
+        assert_eq!(a + (b - a), b);
+        assert_eq!((b - a).signum(), Offset::new(-1, 1));
+    }
+
+    #[test]
+    fn rotation() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Right.turn_right(), Direction::Down);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::UpRight.turn_right(), Direction::DownRight);
+        assert_eq!(Direction::DownLeft.opposite(), Direction::UpRight);
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+        }
+        assert_eq!(
+            Offset::new(3, 1).rotate_right().rotate_right(),
+            Offset::new(-3, -1)
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Point::from((2, 3)), Point::new(2, 3));
+        assert_eq!(Direction::try_from(Offset::new(0, 1)), Ok(Direction::Down));
+        assert_eq!(
+            Direction::try_from(Offset::new(0, 2)),
+            Err(Offset::new(0, 2))
+        );
+        assert_eq!(Offset::from(Direction::Left) * 3, Offset::new(-3, 0));
+        let around: Vec<Point> = Point::default().neighbors(&Direction::ORTHOGONAL).collect();
+        assert_eq!(
+            around,
+            vec![(0, -1).into(), (1, 0).into(), (0, 1).into(), (-1, 0).into()]
+        );
+    }
+
+    #[test]
+    fn space() {
+        let a = Point3::new(1, 2, 3);
+        let b = Point3::from((-1, 2, 5));
+        assert_eq!(a.manhattan(&b), 4);
+        assert_eq!(a.least(&b), Point3::new(-1, 2, 3));
+        assert_eq!(a.greatest(&b), Point3::new(1, 2, 5));
+        assert_eq!(a.faces().count(), 6);
+        assert!(a.faces().all(|x| x.manhattan(&a) == 1));
+        assert_eq!(a + b * 2 - a, Point3::new(-2, 4, 10));
+        assert_eq!(-a, Point3::new(-1, -2, -3));
+        assert_eq!(a.dot(&b), 18);
+    }
+}
@@ -0,0 +1,242 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use ndarray::Array2;
+
+use crate::{
+    geometry::{Direction, Point},
+    parse::{ParseError, Span},
+};
+
+/// dense 2D grid, where cells are addressed by points from `(0, 0)` at the top-left corner
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T>(Array2<T>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError<K> {
+    Empty,
+    RaggedRow { expected: usize, found: usize },
+    InvalidCell(K),
+}
+
+impl<K: Display> Display for GridError<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "expected at least one row"),
+            Self::RaggedRow { expected, found } => {
+                write!(f, "expected {expected} cells in the row, found {found}")
+            }
+            Self::InvalidCell(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self(Array2::from_elem((height, width), fill))
+    }
+}
+
+impl<T> Grid<T> {
+    /// grid drawn as a character map, one row per line, where `cell` reads each character
+    pub fn parse<K>(
+        input: &str,
+        cell: impl Fn(char) -> Result<T, K>,
+    ) -> Result<Self, ParseError<GridError<K>>> {
+        let input = input.trim_end();
+        let width = input.lines().next().map(|x| x.chars().count()).unwrap_or(0);
+        if width == 0 {
+            return Err(ParseError::new(GridError::Empty, Span::new(1, 1, 1)));
+        }
+        let mut cells = Vec::with_capacity(input.len());
+        for (y, line) in input.lines().enumerate() {
+            let found = line.chars().count();
+            if found != width {
+                return Err(ParseError::new(
+                    GridError::RaggedRow {
+                        expected: width,
+                        found,
+                    },
+                    Span::new(y + 1, width.min(found) + 1, 1),
+                ));
+            }
+            for (x, c) in line.chars().enumerate() {
+                cells.push(cell(c).map_err(|e| {
+                    ParseError::new(GridError::InvalidCell(e), Span::new(y + 1, x + 1, 1))
+                })?);
+            }
+        }
+        let height = cells.len() / width;
+        Ok(Self(
+            Array2::from_shape_vec((height, width), cells).expect("rows of the same width"),
+        ))
+    }
+    pub fn width(&self) -> usize {
+        self.0.ncols()
+    }
+    pub fn height(&self) -> usize {
+        self.0.nrows()
+    }
+    /// whether `at` lies within the grid
+    pub fn contains(&self, at: Point) -> bool {
+        self.index(at).is_some()
+    }
+    pub fn get(&self, at: Point) -> Option<&T> {
+        self.0.get(self.index(at)?)
+    }
+    pub fn get_mut(&mut self, at: Point) -> Option<&mut T> {
+        let index = self.index(at)?;
+        self.0.get_mut(index)
+    }
+    /// neighbor of `at` toward a direction, unless it falls outside of the grid
+    pub fn step(&self, at: Point, toward: Direction) -> Option<Point> {
+        Some(at.step(toward)).filter(|x| self.contains(*x))
+    }
+    /// neighbors of `at` among `directions`, e.g. [`Direction::ORTHOGONAL`] or [`Direction::ALL`]
+    pub fn neighbors<'a>(
+        &'a self,
+        at: Point,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = Point> + 'a {
+        directions.iter().filter_map(move |x| self.step(at, *x))
+    }
+    /// cells met when walking from `at` toward a direction until the edge, `at` excluded
+    pub fn ray(&self, at: Point, toward: Direction) -> impl Iterator<Item = Point> + '_ {
+        std::iter::successors(self.step(at, toward), move |x| self.step(*x, toward))
+    }
+    /// every cell, row after row
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let width = self.width() as isize;
+        (0..self.height() as isize).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.0
+            .indexed_iter()
+            .map(|((y, x), v)| (Point::new(x as isize, y as isize), v))
+    }
+    /// row and column in the underlying array
+    fn index(&self, at: Point) -> Option<(usize, usize)> {
+        let (x, y) = (usize::try_from(at.x).ok()?, usize::try_from(at.y).ok()?);
+        (x < self.width() && y < self.height()).then_some((y, x))
+    }
+    /// character map of the grid, one row per line
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+        self.0
+            .rows()
+            .into_iter()
+            .map(|row| row.iter().map(&cell).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+    fn index(&self, at: Point) -> &Self::Output {
+        self.get(at).expect("point within the grid")
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, at: Point) -> &mut Self::Output {
+        self.get_mut(at).expect("point within the grid")
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, row) in self.0.rows().into_iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            for cell in row.iter() {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::Span;
+
+    use crate::geometry::{Direction, Point};
+
+    use super::{Grid, GridError};
+
+    const INPUT: &str = "#..
+.#.
+..#
+#..";
+
+    fn parse(input: &str) -> Grid<bool> {
+        Grid::parse(input, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(c),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_render() {
+        let grid = parse(INPUT);
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert!(grid[Point::new(1, 1)] && grid[Point::new(0, 3)] && !grid[Point::new(2, 3)]);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert!(!grid.contains(Point::new(-1, 0)));
+        assert_eq!(grid.render(|x| if *x { '#' } else { '.' }), INPUT);
+        let err = Grid::parse("#.\n#", Ok::<_, char>).unwrap_err();
+        assert_eq!(
+            err.kind,
+            GridError::RaggedRow {
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(err.span, Span::new(2, 2, 1));
+        let err = Grid::parse("#.\n.x", |c| "#.".contains(c).then_some(c).ok_or(c)).unwrap_err();
+        assert_eq!(err.kind, GridError::InvalidCell('x'));
+        assert_eq!(err.span, Span::new(2, 2, 1));
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = parse(INPUT);
+        let corner: Vec<_> = grid
+            .neighbors(Point::new(0, 0), &Direction::ORTHOGONAL)
+            .collect();
+        assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.neighbors(Point::new(0, 0), &Direction::ALL).count(), 3);
+        assert_eq!(grid.neighbors(Point::new(1, 1), &Direction::ALL).count(), 8);
+        assert_eq!(
+            grid.neighbors(Point::new(2, 3), &Direction::ORTHOGONAL)
+                .count(),
+            2
+        );
+        assert_eq!(grid.step(Point::new(2, 3), Direction::DownRight), None);
+    }
+
+    #[test]
+    fn ray() {
+        let grid = parse(INPUT);
+        let diagonal: Vec<_> = grid.ray(Point::new(0, 0), Direction::DownRight).collect();
+        assert_eq!(diagonal, vec![Point::new(1, 1), Point::new(2, 2)]);
+        assert!(diagonal.iter().all(|x| grid[*x]));
+        assert_eq!(grid.ray(Point::new(0, 1), Direction::Left).count(), 0);
+        assert_eq!(grid.ray(Point::new(0, 0), Direction::Down).count(), 3);
+    }
+
+    #[test]
+    fn display() {
+        let mut grid = Grid::new(3, 2, 0);
+        grid[Point::new(2, 1)] = 7;
+        *grid.get_mut(Point::new(0, 0)).unwrap() = 1;
+        assert_eq!(grid.to_string(), "100\n007");
+        assert_eq!(grid.positions().nth(4), Some(Point::new(1, 1)));
+        assert_eq!(grid.iter().filter(|(_, x)| **x > 0).count(), 2);
+    }
+}
@@ -0,0 +1,270 @@
+use std::{
+    fmt::Display,
+    fs::{read_dir, read_to_string},
+    io::{self, Read},
+    path::PathBuf,
+};
+
+/// year of the puzzles, as found in the `inputs/<year>/day<N>.txt` layout
+pub const YEAR: u16 = 2022;
+
+/// environment variable pointing at a directory of inputs
+pub const INPUT_DIR: &str = "AOC_INPUT_DIR";
+
+/// where a puzzle input is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    Path(PathBuf),
+}
+
+impl From<&str> for Source {
+    fn from(v: &str) -> Self {
+        match v {
+            "-" => Self::Stdin,
+            path => Self::Path(PathBuf::from(path)),
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Stdin => write!(f, "stdin"),
+            Self::Path(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// puzzle input along with where it was found
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub source: Source,
+    pub text: String,
+}
+
+/// every location tried for a day, none of which could be read
+#[derive(Debug)]
+pub struct NotFound {
+    pub day: u8,
+    pub tried: Vec<(Source, io::Error)>,
+}
+
+impl NotFound {
+    /// whether no location exists at all, rather than one existing but failing to read
+    pub fn missing(&self) -> bool {
+        self.tried
+            .iter()
+            .all(|(_, e)| e.kind() == io::ErrorKind::NotFound)
+    }
+}
+
+impl Display for NotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cannot read input for day {}, tried:", self.day)?;
+        for (source, e) in self.tried.iter() {
+            write!(f, "\n  - {source} ({e})")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for NotFound {}
+
+/// finds the input of a day, from the first of:
+/// - an explicit path, or `-` for stdin
+/// - `$AOC_INPUT_DIR/day.<N>.txt` or `$AOC_INPUT_DIR/day<N>.txt`
+/// - `inputs/<year>/day<N>.txt`
+/// - `day.<N>.txt`
+///
+/// or, for a named input set, from the first of:
+/// - `$AOC_INPUT_DIR/<set>/day.<N>.txt` or `$AOC_INPUT_DIR/<set>/day<N>.txt`
+/// - `inputs/<set>/day.<N>.txt` or `inputs/<set>/day<N>.txt`
+#[derive(Debug, Clone, Default)]
+pub struct Resolver {
+    pub explicit: Option<Source>,
+    pub dir: Option<PathBuf>,
+    /// directory the conventional locations are relative to
+    pub root: PathBuf,
+    /// named input set, e.g. one per account
+    pub set: Option<String>,
+}
+
+impl Resolver {
+    /// resolves relative to the current directory, honoring `AOC_INPUT_DIR`
+    pub fn from_env(explicit: Option<Source>) -> Self {
+        Self {
+            explicit,
+            dir: std::env::var_os(INPUT_DIR).map(PathBuf::from),
+            root: PathBuf::from("."),
+            set: None,
+        }
+    }
+    /// same locations, restricted to the input set `name`
+    pub fn with_set(&self, name: &str) -> Self {
+        Self {
+            set: Some(name.to_string()),
+            ..self.clone()
+        }
+    }
+    /// names of the input sets found, sorted
+    pub fn sets(&self) -> Vec<String> {
+        let year = YEAR.to_string();
+        let mut sets: Vec<String> = self
+            .dir
+            .iter()
+            .chain(std::iter::once(&self.root.join("inputs")))
+            .filter_map(|dir| read_dir(dir).ok())
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+            .filter(|name| *name != year)
+            .collect();
+        sets.sort();
+        sets.dedup();
+        sets
+    }
+    /// locations tried for `day`, in order
+    pub fn candidates(&self, day: u8) -> Vec<Source> {
+        if let Some(explicit) = &self.explicit {
+            return vec![explicit.clone()];
+        }
+        let mut candidates = vec![];
+        if let Some(set) = &self.set {
+            for dir in self
+                .dir
+                .iter()
+                .chain(std::iter::once(&self.root.join("inputs")))
+            {
+                candidates.push(Source::Path(dir.join(set).join(format!("day.{day}.txt"))));
+                candidates.push(Source::Path(dir.join(set).join(format!("day{day}.txt"))));
+            }
+            return candidates;
+        }
+        if let Some(dir) = &self.dir {
+            candidates.push(Source::Path(dir.join(format!("day.{day}.txt"))));
+            candidates.push(Source::Path(dir.join(format!("day{day}.txt"))));
+        }
+        candidates.push(Source::Path(
+            self.root
+                .join("inputs")
+                .join(YEAR.to_string())
+                .join(format!("day{day}.txt")),
+        ));
+        candidates.push(Source::Path(self.root.join(format!("day.{day}.txt"))));
+        candidates
+    }
+    pub fn read(&self, day: u8) -> Result<Input, NotFound> {
+        let mut tried = vec![];
+        for source in self.candidates(day) {
+            match read(&source) {
+                Ok(text) => return Ok(Input { source, text }),
+                Err(e) => tried.push((source, e)),
+            }
+        }
+        Err(NotFound { day, tried })
+    }
+}
+
+fn read(source: &Source) -> io::Result<String> {
+    match source {
+        Source::Stdin => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text)?;
+            Ok(text)
+        }
+        Source::Path(path) => read_to_string(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::{Resolver, Source};
+
+    fn scratch(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("aoc-input-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("inputs/2022")).unwrap();
+        fs::create_dir_all(root.join("custom")).unwrap();
+        root
+    }
+
+    #[test]
+    fn source() {
+        assert_eq!(Source::from("-"), Source::Stdin);
+        assert_eq!(Source::from("day.1.txt"), Source::Path("day.1.txt".into()));
+    }
+
+    #[test]
+    fn precedence() {
+        let root = scratch("precedence");
+        fs::write(root.join("day.3.txt"), "legacy").unwrap();
+        let mut resolver = Resolver {
+            root: root.clone(),
+            ..Default::default()
+        };
+        assert_eq!(resolver.read(3).unwrap().text, "legacy");
+        fs::write(root.join("inputs/2022/day3.txt"), "conventional").unwrap();
+        assert_eq!(resolver.read(3).unwrap().text, "conventional");
+        resolver.dir = Some(root.join("custom"));
+        fs::write(root.join("custom/day3.txt"), "from env").unwrap();
+        assert_eq!(resolver.read(3).unwrap().text, "from env");
+        resolver.explicit = Some(Source::Path(root.join("day.3.txt")));
+        let input = resolver.read(3).unwrap();
+        assert_eq!(input.text, "legacy");
+        assert_eq!(input.source, Source::Path(root.join("day.3.txt")));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn not_found() {
+        let root = scratch("not-found");
+        let resolver = Resolver {
+            dir: Some(root.join("custom")),
+            root: root.clone(),
+            ..Default::default()
+        };
+        let err = resolver.read(12).unwrap_err();
+        assert_eq!(err.tried.len(), 4);
+        let message = err.to_string();
+        assert!(message.starts_with("cannot read input for day 12, tried:\n  - "));
+        for path in [
+            "custom/day.12.txt",
+            "custom/day12.txt",
+            "inputs/2022/day12.txt",
+            "day.12.txt",
+        ] {
+            assert!(message.contains(&root.join(path).display().to_string()));
+        }
+        assert!(err.missing());
+        fs::create_dir_all(root.join("day.12.txt")).unwrap();
+        assert!(!resolver.read(12).unwrap_err().missing());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn sets() {
+        let root = scratch("sets");
+        fs::create_dir_all(root.join("inputs/bob")).unwrap();
+        fs::create_dir_all(root.join("inputs/alice")).unwrap();
+        fs::create_dir_all(root.join("custom/carol")).unwrap();
+        fs::write(root.join("inputs/alice/day.7.txt"), "alice").unwrap();
+        fs::write(root.join("custom/carol/day7.txt"), "carol").unwrap();
+        fs::write(root.join("inputs/2022/day7.txt"), "default").unwrap();
+        let resolver = Resolver {
+            dir: Some(root.join("custom")),
+            root: root.clone(),
+            ..Default::default()
+        };
+        assert_eq!(resolver.sets(), vec!["alice", "bob", "carol"]);
+        assert_eq!(resolver.read(7).unwrap().text, "default");
+        assert_eq!(resolver.with_set("alice").read(7).unwrap().text, "alice");
+        assert_eq!(resolver.with_set("carol").read(7).unwrap().text, "carol");
+        let err = resolver.with_set("bob").read(7).unwrap_err();
+        assert_eq!(err.tried.len(), 4);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
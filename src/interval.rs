@@ -0,0 +1,197 @@
+use std::{fmt::Display, ops::RangeInclusive};
+
+use num_traits::PrimInt;
+
+/// range of integers, both bounds included and never empty
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    /// interval from `start` to `end`, if not empty
+    pub fn new(start: T, end: T) -> Option<Self> {
+        (start <= end).then_some(Self { start, end })
+    }
+    /// number of integers in the interval
+    pub fn len(&self) -> T {
+        self.end - self.start + T::one()
+    }
+    pub fn contains(&self, v: T) -> bool {
+        self.start <= v && v <= self.end
+    }
+    /// whether every integer of `other` is also in this interval
+    pub fn covers(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+    /// whether both intervals share at least one integer
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+    /// whether both intervals overlap or follow each other without a gap
+    fn touches(&self, other: &Self) -> bool {
+        self.start <= other.end.saturating_add(T::one())
+            && other.start <= self.end.saturating_add(T::one())
+    }
+    /// integers in both intervals, if any
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+}
+
+impl<T: PrimInt> From<RangeInclusive<T>> for Interval<T> {
+    /// interval of a non-empty range
+    fn from(v: RangeInclusive<T>) -> Self {
+        let (start, end) = v.into_inner();
+        Self::new(start, end).expect("non-empty range")
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..={}", self.start, self.end)
+    }
+}
+
+/// union of intervals, kept sorted with no two of them overlapping or touching
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalSet<T>(Vec<Interval<T>>);
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self(vec![])
+    }
+    /// adds `interval`, merging it with the intervals it touches
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let from = self
+            .0
+            .partition_point(|x| !x.touches(&interval) && x.end < interval.start);
+        let to = from + self.0[from..].partition_point(|x| x.touches(&interval));
+        let merged = self.0[from..to].iter().fold(interval, |acc, x| Interval {
+            start: acc.start.min(x.start),
+            end: acc.end.max(x.end),
+        });
+        self.0.splice(from..to, [merged]);
+    }
+    /// disjoint intervals, in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> + '_ {
+        self.0.iter()
+    }
+    /// number of integers in the union
+    pub fn len(&self) -> T {
+        self.0.iter().fold(T::zero(), |acc, x| acc + x.len())
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn contains(&self, v: T) -> bool {
+        let idx = self.0.partition_point(|x| x.end < v);
+        self.0.get(idx).is_some_and(|x| x.contains(v))
+    }
+    /// intervals within `bounds` not covered by the union
+    pub fn gaps(&self, bounds: Interval<T>) -> impl Iterator<Item = Interval<T>> + '_ {
+        let mut next = Some(bounds.start);
+        self.0.iter().map(Some).chain([None]).filter_map(move |x| {
+            let from = next?;
+            match x {
+                Some(x) => {
+                    let gap = x
+                        .start
+                        .checked_sub(&T::one())
+                        .and_then(|end| Interval::new(from, end.min(bounds.end)));
+                    if x.end >= from {
+                        next = x.end.checked_add(&T::one()).filter(|x| *x <= bounds.end);
+                    }
+                    gap
+                }
+                None => {
+                    next = None;
+                    Interval::new(from, bounds.end)
+                }
+            }
+        })
+    }
+}
+
+impl<T: PrimInt> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut intervals: Vec<Interval<T>> = iter.into_iter().collect();
+        intervals.sort();
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if last.touches(&interval) => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        Self(merged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Interval, IntervalSet};
+
+    #[test]
+    fn interval() {
+        let a = Interval::from(2..=8);
+        let b = Interval::from(3..=7);
+        assert_eq!(a.len(), 7);
+        assert!(a.covers(&b) && !b.covers(&a));
+        assert!(a.overlaps(&Interval::from(8..=9)));
+        assert!(!a.overlaps(&Interval::from(9..=9)));
+        assert_eq!(a.intersection(&Interval::from(6..=12)), Interval::new(6, 8));
+        assert_eq!(a.intersection(&Interval::from(9..=12)), None);
+        assert_eq!(Interval::new(3, 2), None);
+        assert_eq!(Interval::from(-2..=2).to_string(), "-2..=2");
+    }
+
+    #[test]
+    fn union() {
+        let mut set: IntervalSet<i32> = [12..=12, 2..=2, -2..=2, 16..=24, 14..=18]
+            .into_iter()
+            .map(Interval::from)
+            .collect();
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![(-2..=2).into(), (12..=12).into(), (14..=24).into()]
+        );
+        assert_eq!(set.len(), 17);
+        assert!(set.contains(-2) && set.contains(20));
+        assert!(!set.contains(3) && !set.contains(13) && !set.contains(25));
+        set.insert((3..=11).into());
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![(-2..=12).into(), (14..=24).into()]
+        );
+        set.insert((30..=31).into());
+        set.insert((-9..=-8).into());
+        assert_eq!(set.iter().count(), 4);
+        assert_eq!(set.len(), 15 + 11 + 2 + 2);
+    }
+
+    #[test]
+    fn gaps() {
+        let set: IntervalSet<i32> = [0..=2, 5..=6, 9..=9]
+            .into_iter()
+            .map(Interval::from)
+            .collect();
+        let gaps = |from, to| set.gaps(Interval::from(from..=to)).collect::<Vec<_>>();
+        assert_eq!(gaps(0, 9), vec![(3..=4).into(), (7..=8).into()]);
+        assert_eq!(
+            gaps(-1, 12),
+            vec![
+                (-1..=-1).into(),
+                (3..=4).into(),
+                (7..=8).into(),
+                (10..=12).into()
+            ]
+        );
+        assert_eq!(gaps(5, 6), vec![]);
+        assert_eq!(gaps(4, 4), vec![(4..=4).into()]);
+        assert!(IntervalSet::<i32>::new()
+            .gaps((1..=3).into())
+            .eq([(1..=3).into()]));
+    }
+}
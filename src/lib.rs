@@ -0,0 +1,47 @@
+//! Advent of Code 2022 solutions, one module per day.
+
+pub mod automaton;
+pub mod batch;
+pub mod bench;
+pub mod circular;
+pub mod cycle;
+pub mod day_1;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_2;
+pub mod day_20;
+pub mod day_21;
+pub mod day_22;
+pub mod day_23;
+pub mod day_25;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+pub mod diagnostics;
+pub mod expression;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod output;
+pub mod parallel;
+pub mod parse;
+pub mod pathfinding;
+pub mod picture;
+pub mod registry;
+pub mod scaffold;
+pub mod solution;
+pub mod verify;
+pub mod voxel;
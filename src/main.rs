@@ -0,0 +1,260 @@
+use std::{fs::read_to_string, path::PathBuf, process::ExitCode, time::Instant};
+
+use anyhow::{bail, Context};
+use clap::{Parser, Subcommand};
+use colored::Colorize;
+
+use adventofcode2022::{
+    batch::Batch,
+    bench::Bench,
+    diagnostics::Diagnostic,
+    input::{Resolver, Source},
+    output::{Format, Record},
+    registry::{self, Day, DAYS},
+    scaffold::Scaffold,
+    verify::{Answers, Check, Report},
+};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solution for a day and part
+    Run {
+        #[arg(required_unless_present = "all")]
+        day: Option<u8>,
+        #[arg(required_unless_present = "all")]
+        part: Option<u8>,
+        /// Puzzle input, or `-` for stdin, defaults to $AOC_INPUT_DIR/day.<day>.txt,
+        /// inputs/2022/day<day>.txt then ./day.<day>.txt
+        #[arg(long, conflicts_with = "all")]
+        input: Option<String>,
+        /// Named input set, read from inputs/<set>/day.<day>.txt
+        #[arg(long, conflicts_with = "input")]
+        set: Option<String>,
+        /// Run every part of every day
+        #[arg(long, conflicts_with_all = ["day", "part"])]
+        all: bool,
+        /// Print answers as text, json or ndjson records of {day, part, answer, elapsed_ms}
+        #[arg(long, default_value = "text")]
+        format: Format,
+    },
+    /// List available days and parts
+    List,
+    /// Check every part against its expected answer
+    Verify {
+        /// Days to verify, defaults to all of them
+        days: Vec<u8>,
+        /// Expected answers, keyed by day then part
+        #[arg(long, default_value = "answers.toml")]
+        answers: PathBuf,
+    },
+    /// Run a day against the default input and every input set, side by side
+    Batch { day: u8 },
+    /// Generate and register the skeleton of a new day, from the crate root
+    New {
+        day: u8,
+        /// Title of the puzzle, as shown by `list`
+        #[arg(long, default_value = "TODO")]
+        title: String,
+    },
+    /// Time parsing and solving of every part
+    Bench {
+        /// Days to benchmark, defaults to all of them
+        days: Vec<u8>,
+        /// Runs per part
+        #[arg(long, short = 'n', default_value_t = 10)]
+        iterations: usize,
+        /// Print results as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            match e.downcast_ref::<Diagnostic>() {
+                Some(diagnostic) => eprintln!("{diagnostic}"),
+                None => eprintln!("{}: {e:#}", "error".red().bold()),
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> anyhow::Result<()> {
+    match cli.command {
+        Command::Run {
+            day: Some(day),
+            part: Some(part),
+            input,
+            set,
+            format,
+            ..
+        } => {
+            let resolver = Resolver::from_env(input.as_deref().map(Source::from));
+            let resolver = match set {
+                Some(set) => resolver.with_set(&set),
+                None => resolver,
+            };
+            let puzzle = resolver.read(day)?.text;
+            let record = timed(registry::find(day)?, part, puzzle.as_str())?;
+            match format {
+                Format::Text => println!("{}", record.answer),
+                Format::Json => println!("{}", serde_json::to_string_pretty(&record)?),
+                Format::Ndjson => println!("{}", serde_json::to_string(&record)?),
+            }
+        }
+        Command::Run { set, format, .. } => {
+            let resolver = Resolver::from_env(None);
+            let resolver = match set {
+                Some(set) => resolver.with_set(&set),
+                None => resolver,
+            };
+            let mut records = vec![];
+            for day in DAYS {
+                let Some(puzzle) = optional_puzzle(&resolver, day.number)? else {
+                    eprintln!("day {}: skipped: no input", day.number);
+                    continue;
+                };
+                for part in 1..=day.parts {
+                    let record = timed(day, part, puzzle.as_str())?;
+                    match format {
+                        Format::Text => {
+                            println!("day {} part {part}:", day.number);
+                            println!("{}", record.answer);
+                        }
+                        Format::Json => records.push(record),
+                        Format::Ndjson => println!("{}", serde_json::to_string(&record)?),
+                    }
+                }
+            }
+            if format == Format::Json {
+                println!("{}", serde_json::to_string_pretty(&records)?);
+            }
+        }
+        Command::List => {
+            for day in DAYS {
+                let parts: Vec<String> = (1..=day.parts).map(|x| x.to_string()).collect();
+                println!(
+                    "day {}: {} (part {})",
+                    day.number,
+                    day.title,
+                    parts.join(", ")
+                );
+            }
+        }
+        Command::Verify { days, answers } => {
+            for day in days.iter() {
+                registry::find(*day)?;
+            }
+            let answers: Answers = read_to_string(&answers)
+                .with_context(|| format!("cannot read answers at {}", answers.display()))?
+                .parse()
+                .with_context(|| format!("cannot parse answers at {}", answers.display()))?;
+            let resolver = Resolver::from_env(None);
+            let mut report = Report::default();
+            for day in DAYS
+                .iter()
+                .filter(|x| days.is_empty() || days.contains(&x.number))
+            {
+                let puzzle = optional_puzzle(&resolver, day.number);
+                for part in 1..=day.parts {
+                    let check = match &puzzle {
+                        Ok(Some(puzzle)) => {
+                            Check::run(day, part, puzzle, answers.get(day.number, part))
+                        }
+                        Ok(None) => Check::skipped(day.number, part, "no input"),
+                        Err(e) => Check::error(day.number, part, e),
+                    };
+                    report.0.push(check);
+                }
+            }
+            println!("{report}");
+            if report.failures() > 0 {
+                bail!("{} of {} checks failed", report.failures(), report.0.len());
+            }
+        }
+        Command::Batch { day } => {
+            let solved = registry::find(day)?;
+            let resolver = Resolver::from_env(None);
+            let mut inputs = vec![];
+            // a missing default input is left out, one that cannot be read shows as an error
+            if let Some(input) = optional_puzzle(&resolver, day).transpose() {
+                inputs.push(("default".to_string(), input));
+            }
+            for set in resolver.sets() {
+                let input = resolver.with_set(&set).read(day);
+                inputs.push((set, input.map(|x| x.text).map_err(anyhow::Error::from)));
+            }
+            if inputs.is_empty() {
+                bail!("no input for day {day}, nor any input set in inputs/");
+            }
+            println!("{}", Batch::run(solved, inputs));
+        }
+        Command::New { day, title } => {
+            let module = Scaffold::new(".").create(day, &title)?;
+            println!("created {} and registered day {day}", module.display());
+            println!("put its input at inputs/2022/day{day}.txt, then `just test {day}`");
+        }
+        Command::Bench {
+            days,
+            iterations,
+            json,
+        } => {
+            for day in days.iter() {
+                registry::find(*day)?;
+            }
+            let resolver = Resolver::from_env(None);
+            let mut benches = vec![];
+            if !json {
+                println!(
+                    "day  part  phase  {:>12}  {:>12}  {:>12}",
+                    "mean", "min", "max"
+                );
+            }
+            for day in DAYS
+                .iter()
+                .filter(|x| days.is_empty() || days.contains(&x.number))
+            {
+                let Some(puzzle) = optional_puzzle(&resolver, day.number)? else {
+                    eprintln!("day {}: skipped: no input", day.number);
+                    continue;
+                };
+                for part in 1..=day.parts {
+                    let bench = Bench::run(day, part, puzzle.as_str(), iterations)?;
+                    if !json {
+                        println!("{bench}");
+                    }
+                    benches.push(bench);
+                }
+            }
+            if json {
+                println!("{}", serde_json::to_string_pretty(&benches)?);
+            }
+        }
+    };
+    Ok(())
+}
+
+fn timed(day: &Day, part: u8, input: &str) -> anyhow::Result<Record> {
+    let start = Instant::now();
+    let answer = day.run(part, input)?;
+    Ok(Record::new(day.number, part, answer, start.elapsed()))
+}
+
+/// input of a day, or none when no location has one, for commands going over every day
+fn optional_puzzle(resolver: &Resolver, day: u8) -> anyhow::Result<Option<String>> {
+    match resolver.read(day) {
+        Ok(input) => Ok(Some(input.text)),
+        Err(e) if e.missing() => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,82 @@
+use std::{str::FromStr, time::Duration};
+
+use serde::Serialize;
+
+use crate::solution::Answer;
+
+/// how answers are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    /// a single JSON document
+    Json,
+    /// one JSON record per line, as soon as each answer is known
+    Ndjson,
+}
+
+impl FromStr for Format {
+    type Err = String;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        match v {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "ndjson" => Ok(Self::Ndjson),
+            _ => Err(format!("expected text, json or ndjson, found `{v}`")),
+        }
+    }
+}
+
+/// answer to a part, as emitted for scripts
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    pub elapsed_ms: f64,
+}
+
+impl Record {
+    pub fn new(day: u8, part: u8, answer: Answer, elapsed: Duration) -> Self {
+        Self {
+            day,
+            part,
+            answer,
+            elapsed_ms: elapsed.as_secs_f64() * 1_000.,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use crate::{picture::Picture, solution::Answer};
+
+    use super::{Format, Record};
+
+    #[test]
+    fn format() {
+        assert_eq!("ndjson".parse(), Ok(Format::Ndjson));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn json() {
+        let record = Record::new(1, 2, Answer::Number(45_000), Duration::from_micros(1_500));
+        assert_eq!(
+            serde_json::to_value(record).unwrap(),
+            json!({ "day": 1, "part": 2, "answer": 45_000, "elapsed_ms": 1.5 })
+        );
+        let record = Record::new(5, 1, Answer::Text("CMZ".into()), Duration::ZERO);
+        assert_eq!(serde_json::to_value(record).unwrap()["answer"], "CMZ");
+        let picture = Picture::from("##..\n#...");
+        let record = Record::new(10, 2, Answer::Picture(picture), Duration::ZERO);
+        assert_eq!(
+            serde_json::to_value(record).unwrap()["answer"],
+            json!({ "rows": ["##..", "#..."], "text": null })
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use std::{
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+/// `f` applied to every item, spread over as many threads as there are cores,
+/// with results in the same order as `items` whichever thread finishes first
+pub fn map<T, R>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    let threads = thread::available_parallelism()
+        .map_or(1, |x| x.get())
+        .min(items.len());
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = vec![];
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(idx) else {
+                            return done;
+                        };
+                        done.push((idx, f(item)));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|x| x.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect()
+    });
+    results.sort_by_key(|(idx, _)| *idx);
+    results.into_iter().map(|(_, x)| x).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::map;
+
+    #[test]
+    fn ordered() {
+        let items: Vec<u64> = (0..16).collect();
+        let squares = map(&items, |x| {
+            // later items finish first
+            thread::sleep(Duration::from_millis(2 * (16 - x)));
+            x * x
+        });
+        assert_eq!(squares, items.iter().map(|x| x * x).collect::<Vec<_>>());
+        assert!(map(&[] as &[u8], |x| *x).is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+/// location of a token in puzzle input, lines and columns start at 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub len: usize,
+}
+
+impl Span {
+    pub fn new(line: usize, column: usize, len: usize) -> Self {
+        Self { line, column, len }
+    }
+    /// span of `token` in `text`, where `token` is a slice of `text`
+    pub fn of(text: &str, token: &str) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(text.as_ptr() as usize)
+            .filter(|x| x + token.len() <= text.len())
+            .unwrap_or(0);
+        let before = &text[..offset];
+        let start = before.rfind('\n').map_or(0, |x| x + 1);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[start..].chars().count() + 1,
+            len: token.chars().count().max(1),
+        }
+    }
+    /// span right after the end of the first line of `text`, e.g. for a missing token
+    pub fn after(text: &str) -> Self {
+        Self {
+            line: 1,
+            column: text.chars().count() + 1,
+            len: 1,
+        }
+    }
+    /// moves the span down by `lines`
+    pub fn shift(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// error raised while parsing a puzzle input, where `K` is the kind of error of a given day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError<K> {
+    pub span: Span,
+    pub kind: K,
+}
+
+impl<K> ParseError<K> {
+    pub fn new(kind: K, span: Span) -> Self {
+        Self { span, kind }
+    }
+    /// moves the error down by `lines`, when parsed from a slice of the input
+    pub fn shift(mut self, lines: usize) -> Self {
+        self.span = self.span.shift(lines);
+        self
+    }
+    /// moves the error right, when parsed from `token` which is a slice of `text`
+    pub fn within(mut self, text: &str, token: &str) -> Self {
+        self.span.column += Span::of(text, token).column - 1;
+        self
+    }
+    /// same error, with its kind converted, e.g. from a shared parser into a day's own kind
+    pub fn map<L>(self, f: impl FnOnce(K) -> L) -> ParseError<L> {
+        ParseError::new(f(self.kind), self.span)
+    }
+}
+
+impl<K: Display> Display for ParseError<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.span, self.kind)
+    }
+}
+
+impl<K: Debug + Display> std::error::Error for ParseError<K> {}
+
+/// parses every non-blank line of `input`, reporting errors at their line
+pub fn lines<T, K>(input: &str) -> Result<Vec<T>, ParseError<K>>
+where
+    T: FromStr<Err = ParseError<K>>,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| line.parse().map_err(|e: ParseError<K>| e.shift(idx)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lines, ParseError, Span};
+
+    #[test]
+    fn span() {
+        let line = "  move 1 from 2 to x";
+        assert_eq!(Span::of(line, &line[19..]), Span::new(1, 20, 1));
+        assert_eq!(Span::of(line, line.trim()), Span::new(1, 3, 18));
+        assert_eq!(Span::after(line), Span::new(1, 21, 1));
+        assert_eq!(Span::of(line, "elsewhere").column, 1);
+        let text = "one\n  two";
+        assert_eq!(Span::of(text, &text[6..]), Span::new(2, 3, 3));
+    }
+
+    #[test]
+    fn shift() {
+        let input = "1\n2\n\nthree\n4";
+        assert_eq!(lines::<Number, String>("1\n\n2\n").unwrap().len(), 2);
+        let numbers = lines::<Number, String>(input).unwrap_err();
+        assert_eq!(numbers.span, Span::new(4, 1, 5));
+        assert_eq!(numbers.to_string(), "line 4, column 1: three");
+        let line = "1, two";
+        let number = line[3..]
+            .parse::<Number>()
+            .unwrap_err()
+            .within(line, &line[3..]);
+        assert_eq!(number.span, Span::new(1, 4, 3));
+    }
+
+    #[derive(Debug)]
+    struct Number;
+
+    impl std::str::FromStr for Number {
+        type Err = ParseError<String>;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.parse::<u8>()
+                .map(|_| Number)
+                .map_err(|_| ParseError::new(s.to_string(), Span::of(s, s)))
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use num_traits::Zero;
+
+/// nodes walked from a start to a goal, both included, along with the cost of the walk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C = usize> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+impl<N, C> Path<N, C> {
+    /// number of moves, one less than the number of nodes
+    pub fn steps(&self) -> usize {
+        self.nodes.len().saturating_sub(1)
+    }
+}
+
+/// nodes from the start to `goal`, following the node each was reached from
+fn walk_back<N: Eq + Hash + Clone>(parents: &HashMap<N, Option<N>>, goal: N) -> Vec<N> {
+    let mut nodes = vec![goal];
+    while let Some(Some(parent)) = parents.get(nodes.last().expect("at least the goal")) {
+        nodes.push(parent.clone());
+    }
+    nodes.reverse();
+    nodes
+}
+
+/// shortest path in number of moves from any of `starts` to the first node matching `goal`
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: HashMap<N, Option<N>> = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if parents.insert(start.clone(), None).is_none() {
+            queue.push_back(start);
+        }
+    }
+    while let Some(node) = queue.pop_front() {
+        if goal(&node) {
+            let nodes = walk_back(&parents, node);
+            return Some(Path {
+                cost: nodes.len() - 1,
+                nodes,
+            });
+        }
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = parents.entry(next.clone()) {
+                entry.insert(Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// fewest moves from `start` to every node reachable from it, `start` included
+pub fn distances<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                entry.insert(distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// cheapest path from any of `starts` to the first node matching `goal`,
+/// where `successors` gives each neighbor along with the cost of moving to it
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Zero + Add<Output = C> + Ord + Copy,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs: HashMap<N, C> = HashMap::new();
+    let mut parents: HashMap<N, Option<N>> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        costs.insert(start.clone(), C::zero());
+        parents.insert(start.clone(), None);
+        heap.push(Reverse((C::zero(), start)));
+    }
+    while let Some(Reverse((cost, node))) = heap.pop() {
+        if costs.get(&node).is_some_and(|x| *x < cost) {
+            continue;
+        }
+        if goal(&node) {
+            return Some(Path {
+                nodes: walk_back(&parents, node),
+                cost,
+            });
+        }
+        for (next, step) in successors(&node) {
+            let total = cost + step;
+            if costs.get(&next).is_none_or(|x| total < *x) {
+                costs.insert(next.clone(), total);
+                parents.insert(next.clone(), Some(node.clone()));
+                heap.push(Reverse((total, next)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bfs, dijkstra, distances};
+
+    /// 0 -> 1 -> 2 -> 5, 0 -> 3 -> 4 -> 5 with 6 unreachable
+    fn edges(node: &u8) -> Vec<(u8, u32)> {
+        match node {
+            0 => vec![(1, 7), (3, 1)],
+            1 => vec![(2, 1)],
+            2 => vec![(5, 1)],
+            3 => vec![(4, 1)],
+            4 => vec![(5, 9)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn breadth_first() {
+        let successors = |x: &u8| edges(x).into_iter().map(|(x, _)| x);
+        let path = bfs([0], successors, |x| *x == 5).unwrap();
+        assert_eq!(path.steps(), 3);
+        assert_eq!(path.nodes.first(), Some(&0));
+        assert_eq!(path.nodes.last(), Some(&5));
+        assert_eq!(
+            bfs([2, 0], successors, |x| *x == 5).unwrap().nodes,
+            vec![2, 5]
+        );
+        assert_eq!(bfs([0], successors, |x| *x == 6), None);
+    }
+
+    #[test]
+    fn flood() {
+        let successors = |x: &u8| edges(x).into_iter().map(|(x, _)| x);
+        let found = distances(0, successors);
+        assert_eq!(found.len(), 6);
+        assert_eq!((found[&0], found[&1], found[&4], found[&5]), (0, 1, 2, 3));
+        assert!(!found.contains_key(&6));
+        assert_eq!(distances(5, successors).len(), 1);
+    }
+
+    #[test]
+    fn cheapest() {
+        let path = dijkstra([0], edges, |x| *x == 5).unwrap();
+        assert_eq!(path.nodes, vec![0, 1, 2, 5]);
+        assert_eq!(path.cost, 9);
+        let path = dijkstra([0], edges, |x| *x == 4).unwrap();
+        assert_eq!((path.nodes, path.cost), (vec![0, 3, 4], 2));
+        assert_eq!(dijkstra([0], edges, |x| *x == 6), None);
+    }
+}
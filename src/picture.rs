@@ -0,0 +1,103 @@
+use std::fmt::Display;
+
+use serde::Serialize;
+
+/// lit pixels of a letter in the puzzles' font, 4 pixels wide by 6 high
+const FONT: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// answer drawn with `#` and `.`, along with the letters it spells if any
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Picture {
+    pub rows: Vec<String>,
+    pub text: Option<String>,
+}
+
+/// letters spelled by `rows`, each 4 pixels wide and followed by a blank column
+fn decode(rows: &[String]) -> Option<String> {
+    if rows.len() != 6 {
+        return None;
+    }
+    let width = rows.first()?.chars().count();
+    if width == 0 || rows.iter().any(|x| x.chars().count() != width) {
+        return None;
+    }
+    (0..width)
+        .step_by(5)
+        .map(|start| {
+            let glyph: Vec<String> = rows
+                .iter()
+                .map(|row| row.chars().skip(start).take(4).collect())
+                .collect();
+            FONT.iter()
+                .find(|(_, pixels)| glyph.iter().zip(pixels.iter()).all(|(a, b)| a == b))
+                .map(|(letter, _)| *letter)
+        })
+        .collect()
+}
+
+impl From<&str> for Picture {
+    fn from(v: &str) -> Self {
+        let rows: Vec<String> = v
+            .lines()
+            .filter(|x| !x.trim().is_empty())
+            .map(str::to_string)
+            .collect();
+        Self {
+            text: decode(&rows),
+            rows,
+        }
+    }
+}
+
+impl Display for Picture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.rows.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Picture;
+
+    const INPUT: &str = "####.#....###..#....####..##..####.#....
+#....#....#..#.#.......#.#..#....#.#....
+###..#....#..#.#......#..#......#..#....
+#....#....###..#.....#...#.##..#...#....
+#....#....#....#....#....#..#.#....#....
+####.####.#....####.####..###.####.####.
+";
+
+    #[test]
+    fn decode() {
+        let picture = Picture::from(INPUT);
+        assert_eq!(picture.rows.len(), 6);
+        assert_eq!(picture.text.as_deref(), Some("ELPLZGZL"));
+        assert_eq!(picture.to_string(), INPUT.trim_end());
+    }
+
+    #[test]
+    fn undecodable() {
+        assert_eq!(
+            Picture::from(&INPUT.replace("####.####.", "#.##.####.")[..]).text,
+            None
+        );
+        assert_eq!(Picture::from("##..##..\n##..##..").text, None);
+    }
+}
@@ -0,0 +1,147 @@
+use std::any::Any;
+
+use anyhow::{bail, Context};
+
+use crate::{
+    diagnostics::Diagnose,
+    solution::{Answer, Outcome, Solution, Unsolvable},
+};
+
+/// every solved day, in order, as registered by `aoc new`
+pub const DAYS: &[Day] = &[
+    Day::of::<crate::day_1::Puzzle>(),
+    Day::of::<crate::day_2::Puzzle>(),
+    Day::of::<crate::day_3::Puzzle>(),
+    Day::of::<crate::day_4::Puzzle>(),
+    Day::of::<crate::day_5::Puzzle>(),
+    Day::of::<crate::day_6::Puzzle>(),
+    Day::of::<crate::day_7::Puzzle>(),
+    Day::of::<crate::day_8::Puzzle>(),
+    Day::of::<crate::day_9::Puzzle>(),
+    Day::of::<crate::day_10::Puzzle>(),
+    Day::of::<crate::day_11::Puzzle>(),
+    Day::of::<crate::day_12::Puzzle>(),
+    Day::of::<crate::day_13::Puzzle>(),
+    Day::of::<crate::day_14::Puzzle>(),
+    Day::of::<crate::day_15::Puzzle>(),
+    Day::of::<crate::day_16::Puzzle>(),
+    Day::of::<crate::day_17::Puzzle>(),
+    Day::of::<crate::day_18::Puzzle>(),
+    Day::of::<crate::day_19::Puzzle>(),
+    Day::of::<crate::day_20::Puzzle>(),
+    Day::of::<crate::day_21::Puzzle>(),
+    Day::of::<crate::day_22::Puzzle>(),
+    Day::of::<crate::day_23::Puzzle>(),
+    Day::of::<crate::day_25::Puzzle>(),
+];
+
+/// type-erased [`Solution`], so that days can be enumerated
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    pub parts: u8,
+    parse: fn(&str) -> anyhow::Result<Box<dyn Any>>,
+    solve: fn(u8, Box<dyn Any>) -> Result<Answer, Unsolvable>,
+}
+
+/// puzzle input once parsed by a given day
+pub struct Parsed {
+    day: u8,
+    inner: Box<dyn Any>,
+}
+
+pub fn find(day: u8) -> anyhow::Result<&'static Day> {
+    DAYS.iter()
+        .find(|x| x.number == day)
+        .with_context(|| format!("day {day} is not solved"))
+}
+
+fn parse<S: Solution>(input: &str) -> anyhow::Result<Box<dyn Any>> {
+    let parsed = S::parse(input).map_err(|e| e.diagnose(&format!("day {}", S::DAY), input))?;
+    Ok(Box::new(parsed))
+}
+
+fn solve<S: Solution>(part: u8, parsed: Box<dyn Any>) -> Result<Answer, Unsolvable> {
+    let parsed = *parsed
+        .downcast::<S::Parsed>()
+        .expect("input parsed by the same day");
+    match part {
+        1 => S::part_one(parsed).outcome(),
+        _ => S::part_two(parsed).outcome(),
+    }
+}
+
+impl Day {
+    pub const fn of<S: Solution>() -> Self {
+        Self {
+            number: S::DAY,
+            title: S::TITLE,
+            parts: S::PARTS,
+            parse: parse::<S>,
+            solve: solve::<S>,
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> anyhow::Result<Parsed> {
+        Ok(Parsed {
+            day: self.number,
+            inner: (self.parse)(input)?,
+        })
+    }
+
+    pub fn solve(&self, part: u8, parsed: Parsed) -> anyhow::Result<Answer> {
+        if part == 0 || part > self.parts {
+            bail!("day {} has no part {part}", self.number);
+        }
+        if parsed.day != self.number {
+            bail!(
+                "input was parsed by day {}, not {}",
+                parsed.day,
+                self.number
+            );
+        }
+        (self.solve)(part, parsed.inner)
+            .with_context(|| format!("day {} part {part} has no answer", self.number))
+    }
+
+    pub fn run(&self, part: u8, input: &str) -> anyhow::Result<Answer> {
+        self.solve(part, self.parse(input)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diagnostics::Diagnostic;
+
+    use super::{find, DAYS};
+
+    #[test]
+    fn ordered() {
+        let numbers: Vec<u8> = DAYS.iter().map(|x| x.number).collect();
+        let mut sorted = numbers.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(numbers, sorted);
+    }
+
+    #[test]
+    fn lookup() {
+        assert_eq!(find(7).unwrap().title, "No Space Left On Device");
+        assert_eq!(find(25).unwrap().parts, 1);
+        assert!(find(24).is_err());
+        assert!(find(25).unwrap().run(2, "1=").is_err());
+        let err = find(1).unwrap().parse("1000\nabc").err().unwrap();
+        let diagnostic = err.downcast_ref::<Diagnostic>().unwrap();
+        assert_eq!(
+            (diagnostic.origin.as_str(), diagnostic.line.as_str()),
+            ("day 1", "abc")
+        );
+        let parsed = find(1).unwrap().parse("1000").unwrap();
+        assert!(find(2).unwrap().solve(1, parsed).is_err());
+        let err = find(12).unwrap().run(1, "SaE").unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "day 12 part 1 has no answer: no path from the start to the best signal"
+        );
+    }
+}
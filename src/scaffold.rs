@@ -0,0 +1,283 @@
+use std::{
+    fs::{read_to_string, write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+
+/// skeleton of a day, where `__DAY__` and `__TITLE__` are substituted
+const TEMPLATE: &str = r#"use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    diagnostics::Grammar,
+    parse::{lines, ParseError, Span},
+    solution::Solution,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry(i64);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryError {
+    InvalidNumber(String),
+}
+
+impl Display for EntryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidNumber(v) => write!(f, "expected a number, found `{v}`"),
+        }
+    }
+}
+
+impl Grammar for EntryError {
+    fn expected(&self) -> &'static str {
+        "<n>, one per line"
+    }
+}
+
+impl FromStr for Entry {
+    type Err = ParseError<EntryError>;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let token = v.trim();
+        token.parse().map(Self).map_err(|_| {
+            ParseError::new(
+                EntryError::InvalidNumber(token.to_string()),
+                Span::of(v, token),
+            )
+        })
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = __DAY__;
+    const TITLE: &'static str = "__TITLE__";
+
+    type Parsed = Vec<Entry>;
+    type Error = ParseError<EntryError>;
+    type One = i64;
+    type Two = i64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        lines(input)
+    }
+    fn part_one(entries: Self::Parsed) -> Self::One {
+        todo!("part one of day __DAY__, given {} entries", entries.len())
+    }
+    fn part_two(entries: Self::Parsed) -> Self::Two {
+        todo!("part two of day __DAY__, given {} entries", entries.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse::Span, solution::Solution};
+
+    use super::{Entry, EntryError, Puzzle};
+
+    const INPUT: &str = "1
+2
+3";
+
+    #[test]
+    fn parse() {
+        let entries = Puzzle::parse(INPUT).unwrap();
+        assert_eq!(entries, vec![Entry(1), Entry(2), Entry(3)]);
+    }
+
+    #[test]
+    fn part_one() {
+        // expected answer for the example of the puzzle description
+        assert_eq!(Puzzle::part_one(Puzzle::parse(INPUT).unwrap()), 0);
+    }
+
+    #[test]
+    fn part_two() {
+        // expected answer for the example of the puzzle description
+        assert_eq!(Puzzle::part_two(Puzzle::parse(INPUT).unwrap()), 0);
+    }
+
+    #[test]
+    fn invalid() {
+        let err = Puzzle::parse("1\n2x").unwrap_err();
+        assert_eq!(err.kind, EntryError::InvalidNumber("2x".into()));
+        assert_eq!(err.span, Span::new(2, 1, 2));
+    }
+}
+"#;
+
+/// source of the module of a new day
+pub fn template(day: u8, title: &str) -> String {
+    TEMPLATE
+        .replace("__DAY__", &day.to_string())
+        .replace("__TITLE__", &title.replace('"', "\\\""))
+}
+
+/// `lib` with `day` declared among its modules, kept in alphabetical order
+pub fn register_module(lib: &str, day: u8) -> anyhow::Result<String> {
+    let declaration = format!("pub mod day_{day};");
+    let name = format!("day_{day}");
+    let modules: Vec<(usize, &str)> = lib
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| Some((idx, line.strip_prefix("pub mod ")?.strip_suffix(';')?)))
+        .collect();
+    if modules.iter().any(|(_, x)| *x == name) {
+        bail!("day {day} is already declared in src/lib.rs");
+    }
+    let Some(at) = modules
+        .iter()
+        .find(|(_, x)| *x > name.as_str())
+        .map(|(idx, _)| *idx)
+        .or(modules.last().map(|(idx, _)| idx + 1))
+    else {
+        bail!("no module declaration found in src/lib.rs");
+    };
+    Ok(insert(lib, at, &declaration))
+}
+
+/// `registry` with `day` listed among the solved days, kept in numerical order
+pub fn register_day(registry: &str, day: u8) -> anyhow::Result<String> {
+    let entry = |x: u8| format!("    Day::of::<crate::day_{x}::Puzzle>(),");
+    let days: Vec<(usize, u8)> = registry
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            line.trim()
+                .strip_prefix("Day::of::<crate::day_")?
+                .strip_suffix("::Puzzle>(),")?
+                .parse()
+                .ok()
+                .map(|x| (idx, x))
+        })
+        .collect();
+    if days.iter().any(|(_, x)| *x == day) {
+        bail!("day {day} is already registered in src/registry.rs");
+    }
+    let Some(at) = days
+        .iter()
+        .find(|(_, x)| *x > day)
+        .map(|(idx, _)| *idx)
+        .or(days.last().map(|(idx, _)| idx + 1))
+    else {
+        bail!("no registered day found in src/registry.rs");
+    };
+    Ok(insert(registry, at, &entry(day)))
+}
+
+fn insert(text: &str, at: usize, line: &str) -> String {
+    let mut lines: Vec<&str> = text.lines().collect();
+    lines.insert(at, line);
+    lines.join("\n") + "\n"
+}
+
+/// generates and registers new days in the crate found at `root`
+pub struct Scaffold {
+    pub root: PathBuf,
+}
+
+impl Scaffold {
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+    /// writes `src/day_<day>.rs` and registers it, leaving every file untouched
+    /// if the day already exists
+    pub fn create(&self, day: u8, title: &str) -> anyhow::Result<PathBuf> {
+        if !(1..=25).contains(&day) {
+            bail!("day should be between 1 and 25, found {day}");
+        }
+        let src = self.root.join("src");
+        let module = src.join(format!("day_{day}.rs"));
+        if module.exists() || src.join(format!("day_{day}")).exists() {
+            bail!("day {day} already exists, refusing to overwrite it");
+        }
+        let read = |name: &str| {
+            read_to_string(src.join(name))
+                .with_context(|| format!("cannot read {}", src.join(name).display()))
+        };
+        let lib = register_module(&read("lib.rs")?, day)?;
+        let registry = register_day(&read("registry.rs")?, day)?;
+        write(&module, template(day, title))?;
+        write(src.join("lib.rs"), lib)?;
+        write(src.join("registry.rs"), registry)?;
+        Ok(module)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{register_day, register_module, template, Scaffold};
+
+    const LIB: &str = "//! solutions
+
+pub mod bench;
+pub mod day_1;
+pub mod day_25;
+pub mod day_3;
+pub mod diagnostics;
+";
+
+    const REGISTRY: &str = "pub const DAYS: &[Day] = &[
+    Day::of::<crate::day_1::Puzzle>(),
+    Day::of::<crate::day_3::Puzzle>(),
+    Day::of::<crate::day_25::Puzzle>(),
+];
+";
+
+    #[test]
+    fn skeleton() {
+        let source = template(12, "Hill Climbing Algorithm");
+        assert!(source.contains("const DAY: u8 = 12;"));
+        assert!(source.contains("const TITLE: &'static str = \"Hill Climbing Algorithm\";"));
+        assert!(source.contains("#[cfg(test)]\nmod tests {"));
+        assert!(source.contains("const INPUT: &str = "));
+        assert!(!source.contains("__"));
+    }
+
+    #[test]
+    fn register() {
+        let lib = register_module(LIB, 12).unwrap();
+        assert!(lib.contains("pub mod day_1;\npub mod day_12;\npub mod day_25;"));
+        let lib = register_module(LIB, 4).unwrap();
+        assert!(lib.contains("pub mod day_3;\npub mod day_4;\npub mod diagnostics;"));
+        assert!(register_module(LIB, 25).is_err());
+        let registry = register_day(REGISTRY, 12).unwrap();
+        assert!(registry.contains(
+            "day_3::Puzzle>(),\n    Day::of::<crate::day_12::Puzzle>(),\n    Day::of::<crate::day_25"
+        ));
+        let registry = register_day(REGISTRY, 2).unwrap();
+        assert!(registry.contains("day_1::Puzzle>(),\n    Day::of::<crate::day_2::Puzzle>(),"));
+        assert!(register_day(REGISTRY, 3).is_err());
+    }
+
+    #[test]
+    fn refuse_overwrite() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB).unwrap();
+        fs::write(root.join("src/registry.rs"), REGISTRY).unwrap();
+        let scaffold = Scaffold::new(&root);
+        let module = scaffold.create(12, "Hill Climbing Algorithm").unwrap();
+        assert_eq!(module, root.join("src/day_12.rs"));
+        assert!(fs::read_to_string(&module)
+            .unwrap()
+            .contains("DAY: u8 = 12"));
+        fs::write(&module, "solved").unwrap();
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        let err = scaffold.create(12, "again").unwrap_err();
+        assert!(err.to_string().contains("refusing to overwrite"));
+        assert_eq!(fs::read_to_string(&module).unwrap(), "solved");
+        assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), lib);
+        assert!(scaffold.create(3, "registered").is_err());
+        assert!(!root.join("src/day_3.rs").exists());
+        assert!(scaffold.create(26, "too late").is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
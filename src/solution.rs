@@ -0,0 +1,130 @@
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::{diagnostics::Diagnose, picture::Picture};
+
+/// answer to one part of a puzzle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    Picture(Picture),
+}
+
+impl Answer {
+    /// whether `expected`, e.g. read from a file, is this answer,
+    /// as printed or as the letters a picture spells
+    pub fn matches(&self, expected: &Answer) -> bool {
+        let expected = expected.to_string();
+        match self {
+            Answer::Picture(picture) if picture.text.as_ref() == Some(&expected) => true,
+            _ => self.to_string() == expected,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{number}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Picture(picture) => write!(f, "{picture}"),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(v: u32) -> Self {
+        Self::Number(v as i64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(v: usize) -> Self {
+        Self::Number(v as i64)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(v: isize) -> Self {
+        Self::Number(v as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(v: i64) -> Self {
+        Self::Number(v)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(v: String) -> Self {
+        Self::Text(v)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(v: &str) -> Self {
+        Self::Text(v.to_string())
+    }
+}
+
+impl From<Picture> for Answer {
+    fn from(v: Picture) -> Self {
+        Self::Picture(v)
+    }
+}
+
+/// why a part has no answer for an input that parsed fine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsolvable(pub String);
+
+impl Unsolvable {
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self(reason.into())
+    }
+}
+
+impl Display for Unsolvable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for Unsolvable {}
+
+/// what a part returns: an answer, or a result for parts that cannot solve every input
+pub trait Outcome {
+    fn outcome(self) -> Result<Answer, Unsolvable>;
+}
+
+impl<T: Into<Answer>> Outcome for T {
+    fn outcome(self) -> Result<Answer, Unsolvable> {
+        Ok(self.into())
+    }
+}
+
+impl<T: Into<Answer>> Outcome for Result<T, Unsolvable> {
+    fn outcome(self) -> Result<Answer, Unsolvable> {
+        self.map(Into::into)
+    }
+}
+
+/// common entry point implemented by every day
+pub trait Solution {
+    const DAY: u8;
+    const TITLE: &'static str;
+    /// only day 25 has a single part
+    const PARTS: u8 = 2;
+
+    type Parsed: 'static;
+    type Error: std::error::Error + Diagnose + Send + Sync + 'static;
+    type One: Outcome;
+    type Two: Outcome;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error>;
+    fn part_one(parsed: Self::Parsed) -> Self::One;
+    fn part_two(parsed: Self::Parsed) -> Self::Two;
+}
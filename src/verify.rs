@@ -0,0 +1,253 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Context};
+use colored::Colorize;
+
+use crate::{registry::Day, solution::Answer};
+
+/// expected answers for the puzzle inputs, keyed by day then part
+///
+/// ```toml
+/// [1]
+/// 1 = 66616
+/// 2 = 199172
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers(BTreeMap<(u8, u8), Answer>);
+
+impl Answers {
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.0.get(&(day, part))
+    }
+}
+
+impl FromStr for Answers {
+    type Err = anyhow::Error;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let table: toml::Table = v.parse()?;
+        let mut answers = BTreeMap::new();
+        for (day, parts) in table.iter() {
+            let number: u8 = day
+                .parse()
+                .with_context(|| format!("`{day}` is not a day"))?;
+            let Some(parts) = parts.as_table() else {
+                bail!("day {day} should be a table of parts");
+            };
+            for (part, answer) in parts.iter() {
+                let part: u8 = part
+                    .parse()
+                    .with_context(|| format!("`{part}` is not a part of day {day}"))?;
+                let answer = match answer {
+                    toml::Value::Integer(v) => Answer::Number(*v),
+                    toml::Value::String(v) => Answer::Text(v.clone()),
+                    _ => bail!("day {day} part {part} should be a number or a string"),
+                };
+                answers.insert((number, part), answer);
+            }
+        }
+        Ok(Self(answers))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: Answer,
+        found: Answer,
+    },
+    /// nothing to compare against yet
+    Unknown(Answer),
+    /// nothing to run against, e.g. no input
+    Skipped(String),
+    Error(String),
+}
+
+/// outcome of running one part against its expected answer
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    pub elapsed: Duration,
+}
+
+impl Check {
+    pub fn run(day: &Day, part: u8, input: &str, expected: Option<&Answer>) -> Self {
+        let start = Instant::now();
+        let outcome = day.run(part, input);
+        let elapsed = start.elapsed();
+        let status = match (outcome, expected) {
+            (Err(e), _) => Status::Error(format!("{e:#}")),
+            (Ok(found), None) => Status::Unknown(found),
+            (Ok(found), Some(expected)) if found.matches(expected) => Status::Pass,
+            (Ok(found), Some(expected)) => Status::Fail {
+                expected: expected.clone(),
+                found,
+            },
+        };
+        Self {
+            day: day.number,
+            part,
+            status,
+            elapsed,
+        }
+    }
+    /// when the part could not even run, e.g. for lack of input
+    pub fn error(day: u8, part: u8, e: &anyhow::Error) -> Self {
+        Self {
+            day,
+            part,
+            status: Status::Error(format!("{e:#}")),
+            elapsed: Duration::ZERO,
+        }
+    }
+    /// when there is nothing to run against, which is not a failure
+    pub fn skipped(day: u8, part: u8, reason: &str) -> Self {
+        Self {
+            day,
+            part,
+            status: Status::Skipped(reason.to_string()),
+            elapsed: Duration::ZERO,
+        }
+    }
+    pub fn passed(&self) -> bool {
+        self.status == Status::Pass
+    }
+    pub fn failed(&self) -> bool {
+        matches!(self.status, Status::Fail { .. } | Status::Error(_))
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (status, detail) = match &self.status {
+            Status::Pass => ("pass".green(), String::new()),
+            Status::Fail { expected, found } => (
+                "FAIL".red().bold(),
+                format!(
+                    "expected {:?}, found {:?}",
+                    expected.to_string(),
+                    found.to_string()
+                ),
+            ),
+            Status::Unknown(found) => ("????".yellow(), format!("found {:?}", found.to_string())),
+            Status::Skipped(reason) => ("skip".dimmed(), reason.clone()),
+            Status::Error(e) => (
+                "FAIL".red().bold(),
+                e.lines().next().unwrap_or_default().into(),
+            ),
+        };
+        write!(
+            f,
+            "{:>3}  {:>4}  {status}  {:>10.3}ms  {detail}",
+            self.day,
+            self.part,
+            self.elapsed.as_secs_f64() * 1_000.
+        )
+    }
+}
+
+/// checks of every part, printed as a table
+#[derive(Debug, Default)]
+pub struct Report(pub Vec<Check>);
+
+impl Report {
+    pub fn failures(&self) -> usize {
+        self.0.iter().filter(|x| x.failed()).count()
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", "day  part  status        time  detail".bold())?;
+        for check in self.0.iter() {
+            write!(f, "\n{check}")?;
+        }
+        let total: Duration = self.0.iter().map(|x| x.elapsed).sum();
+        let passed = self.0.iter().filter(|x| x.passed()).count();
+        let failed = self.failures();
+        let skipped = self
+            .0
+            .iter()
+            .filter(|x| matches!(x.status, Status::Skipped(_)))
+            .count();
+        write!(
+            f,
+            "\n{passed} passed, {failed} failed, {skipped} skipped, {} without answer in {:.3}ms",
+            self.0.len() - passed - failed - skipped,
+            total.as_secs_f64() * 1_000.
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{registry::find, solution::Answer};
+
+    use super::{Answers, Check, Report, Status};
+
+    const ANSWERS: &str = r#"
+[1]
+1 = 24000
+2 = "45000"
+
+[25]
+1 = "2=-1=0"
+"#;
+
+    const INPUT: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    #[test]
+    fn parse() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+        assert_eq!(answers.get(1, 1), Some(&Answer::Number(24_000)));
+        assert_eq!(answers.get(1, 2), Some(&Answer::Text("45000".into())));
+        assert_eq!(answers.get(25, 2), None);
+        assert!("[one]\n1 = 2".parse::<Answers>().is_err());
+        assert!("[1]\n1 = 2.5".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn check() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+        let day = find(1).unwrap();
+        assert!(Check::run(day, 1, INPUT, answers.get(1, 1)).passed());
+        assert!(Check::run(day, 2, INPUT, answers.get(1, 2)).passed());
+        let check = Check::run(day, 1, INPUT, answers.get(1, 2));
+        assert_eq!(
+            check.status,
+            Status::Fail {
+                expected: Answer::Text("45000".into()),
+                found: Answer::Number(24_000)
+            }
+        );
+        let check = Check::run(day, 1, "abc", answers.get(1, 1));
+        assert!(matches!(check.status, Status::Error(_)));
+        assert!(check.failed());
+        let check = Check::run(day, 1, INPUT, None);
+        assert_eq!(check.status, Status::Unknown(Answer::Number(24_000)));
+        assert!(!check.passed() && !check.failed());
+        let check = Check::skipped(12, 1, "no input");
+        assert!(!check.passed() && !check.failed());
+        assert_eq!(Report(vec![check]).failures(), 0);
+    }
+}
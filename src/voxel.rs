@@ -0,0 +1,114 @@
+use std::collections::HashSet;
+
+use crate::{geometry::Point3, pathfinding::distances};
+
+/// set of unit cubes in space, each at a point
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Voxels(HashSet<Point3>);
+
+impl Voxels {
+    pub fn new() -> Self {
+        Self(HashSet::new())
+    }
+    /// adds a voxel, returning whether there was none yet
+    pub fn insert(&mut self, at: Point3) -> bool {
+        self.0.insert(at)
+    }
+    pub fn contains(&self, at: &Point3) -> bool {
+        self.0.contains(at)
+    }
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &Point3> + '_ {
+        self.0.iter()
+    }
+    /// lowest and highest corners of the box holding every voxel
+    pub fn bounds(&self) -> Option<(Point3, Point3)> {
+        let first = *self.0.iter().next()?;
+        Some(self.0.iter().fold((first, first), |(min, max), x| {
+            (min.least(x), max.greatest(x))
+        }))
+    }
+    /// faces not shared with another voxel
+    pub fn surface(&self) -> usize {
+        self.0
+            .iter()
+            .flat_map(|x| x.faces())
+            .filter(|x| !self.contains(x))
+            .count()
+    }
+    /// empty positions reachable from outside the box holding every voxel
+    pub fn outside(&self) -> HashSet<Point3> {
+        let Some((min, max)) = self.bounds() else {
+            return HashSet::new();
+        };
+        let min = Point3::new(min.x - 1, min.y - 1, min.z - 1);
+        let max = Point3::new(max.x + 1, max.y + 1, max.z + 1);
+        let within = move |x: &Point3| x.least(&min) == min && x.greatest(&max) == max;
+        distances(min, |x| {
+            x.faces()
+                .filter(|x| within(x) && !self.contains(x))
+                .collect::<Vec<_>>()
+        })
+        .into_keys()
+        .collect()
+    }
+    /// faces reachable from outside, leaving out those of pockets of air trapped inside
+    pub fn exterior(&self) -> usize {
+        let outside = self.outside();
+        self.0
+            .iter()
+            .flat_map(|x| x.faces())
+            .filter(|x| outside.contains(x))
+            .count()
+    }
+}
+
+impl FromIterator<Point3> for Voxels {
+    fn from_iter<I: IntoIterator<Item = Point3>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::Point3;
+
+    use super::Voxels;
+
+    #[test]
+    fn surface() {
+        let mut voxels = Voxels::new();
+        assert_eq!(
+            (voxels.surface(), voxels.exterior(), voxels.bounds()),
+            (0, 0, None)
+        );
+        assert!(voxels.insert(Point3::new(1, 1, 1)));
+        assert!(!voxels.insert(Point3::new(1, 1, 1)));
+        assert_eq!(voxels.surface(), 6);
+        voxels.insert(Point3::new(2, 1, 1));
+        assert_eq!(voxels.surface(), 10);
+        assert_eq!(voxels.exterior(), 10);
+    }
+
+    #[test]
+    fn hollow() {
+        // 3x3x3 cube with its center missing
+        let voxels: Voxels = (0..27)
+            .map(|x| Point3::new(x % 3, x / 3 % 3, x / 9))
+            .filter(|x| *x != Point3::new(1, 1, 1))
+            .collect();
+        assert_eq!(voxels.len(), 26);
+        assert_eq!(
+            voxels.bounds(),
+            Some((Point3::new(0, 0, 0), Point3::new(2, 2, 2)))
+        );
+        assert_eq!(voxels.surface(), 54 + 6);
+        assert_eq!(voxels.exterior(), 54);
+        assert!(!voxels.outside().contains(&Point3::new(1, 1, 1)));
+    }
+}